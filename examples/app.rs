@@ -1,6 +1,7 @@
 use std::io::Result;
 
 use voidtui::{
+    command::Command,
     style::color::Color, vertical, widget::{
         app::{App, Program},
         element::Element,
@@ -23,11 +24,12 @@ struct Counter {
 }
 
 impl<'a> Program<'a, Message> for Counter {
-    fn update(&mut self, message: Message) {
+    fn update(&mut self, message: Message) -> Command<Message> {
         match message {
             Message::Increase => self.count += 1,
             Message::Decrease => self.count -= 1,
         }
+        Command::none()
     }
 
     fn view(&self) -> Element<'a, Message> {
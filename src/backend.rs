@@ -0,0 +1,3 @@
+pub mod backend;
+pub mod crossterm_backend;
+pub mod test_backend;
@@ -0,0 +1,37 @@
+use std::io::Result;
+
+use crossterm::event::Event;
+
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::size::Size,
+    style::{color::ColorSystem, theme::Theme},
+};
+
+/// Where an app draws its frames and reads its events from.
+pub trait Backend {
+    /// Prepare the terminal for the app, e.g. enter the alternate screen.
+    fn init(&mut self) -> Result<()>;
+
+    /// Restore the terminal to its original state.
+    fn restore(&mut self) -> Result<()>;
+
+    /// Returns the size of the terminal.
+    fn size(&self) -> Result<Size>;
+
+    /// Returns the color system supported by the terminal.
+    fn color_system(&self) -> ColorSystem;
+
+    /// Draw a frame. `previous` is the last frame drawn if it has the same size, so
+    /// that only the difference can be drawn.
+    fn draw(
+        &mut self,
+        frame: &Buffer,
+        previous: Option<&Buffer>,
+        system: ColorSystem,
+        theme: &Theme,
+    ) -> Result<()>;
+
+    /// Returns the next pending event without blocking, if any.
+    fn poll_event(&mut self) -> Result<Option<Event>>;
+}
@@ -0,0 +1,88 @@
+use std::{
+    io::{stdout, Result, Write},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
+    terminal::{
+        self, disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
+    QueueableCommand,
+};
+
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::size::Size,
+    style::{color::ColorSystem, theme::Theme},
+};
+
+use super::backend::Backend;
+
+/// The backend drawing to the standard output in fullscreen mode.
+#[derive(Debug, Default)]
+pub struct CrosstermBackend;
+
+impl CrosstermBackend {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Backend for CrosstermBackend {
+    fn init(&mut self) -> Result<()> {
+        enable_raw_mode()?;
+        stdout().queue(cursor::Hide)?;
+        stdout().queue(EnableMouseCapture)?;
+        stdout().queue(EnterAlternateScreen)?;
+        stdout().flush()?;
+        Ok(())
+    }
+
+    fn restore(&mut self) -> Result<()> {
+        stdout().queue(LeaveAlternateScreen)?;
+        stdout().queue(DisableMouseCapture)?;
+        stdout().queue(cursor::Show)?;
+        stdout().flush()?;
+        disable_raw_mode()?;
+        Ok(())
+    }
+
+    fn size(&self) -> Result<Size> {
+        Ok(terminal::size()?.into())
+    }
+
+    fn color_system(&self) -> ColorSystem {
+        crate::terminal::detect_color_system()
+    }
+
+    fn draw(
+        &mut self,
+        frame: &Buffer,
+        previous: Option<&Buffer>,
+        system: ColorSystem,
+        theme: &Theme,
+    ) -> Result<()> {
+        // Without a previous frame, clear the screen and diff against an empty frame.
+        let mut stdout = stdout().lock();
+        let out = match previous {
+            Some(previous) => frame.view_diff(previous, system, theme),
+            None => {
+                stdout.queue(Clear(ClearType::All))?;
+                frame.view_diff(&Buffer::new(frame.width(), frame.height()), system, theme)
+            }
+        };
+        stdout.write_all(out.as_bytes())?;
+        stdout.flush()
+    }
+
+    fn poll_event(&mut self) -> Result<Option<Event>> {
+        if event::poll(Duration::ZERO)? {
+            Ok(Some(event::read()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::{collections::VecDeque, io::Result};
+
+use crossterm::event::Event;
+
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::size::Size,
+    style::{color::ColorSystem, theme::Theme},
+};
+
+use super::backend::Backend;
+
+/// An in-memory terminal of a fixed size, to run apps without a TTY, e.g. in tests.
+/// Events are injected with [`Self::push_event`], and the last drawn frame can be
+/// inspected with [`Self::buffer`].
+#[derive(Debug, Clone)]
+pub struct TestBackend {
+    buffer: Buffer,
+    events: VecDeque<Event>,
+}
+
+impl TestBackend {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            buffer: Buffer::new(width, height),
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Get the last drawn frame.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Queue an event, it will be processed by the next step of the app.
+    pub fn push_event(&mut self, event: Event) -> &mut Self {
+        self.events.push_back(event);
+        self
+    }
+
+    /// Resize the terminal, and queue the corresponding resize event.
+    pub fn resize(&mut self, width: u16, height: u16) -> &mut Self {
+        self.buffer = Buffer::new(width, height);
+        self.push_event(Event::Resize(width, height))
+    }
+}
+
+impl Backend for TestBackend {
+    fn init(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn restore(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> Result<Size> {
+        Ok(Size::new(self.buffer.width(), self.buffer.height()))
+    }
+
+    fn color_system(&self) -> ColorSystem {
+        ColorSystem::TrueColor
+    }
+
+    #[allow(unused_variables)]
+    fn draw(
+        &mut self,
+        frame: &Buffer,
+        previous: Option<&Buffer>,
+        system: ColorSystem,
+        theme: &Theme,
+    ) -> Result<()> {
+        self.buffer = frame.clone();
+        Ok(())
+    }
+
+    fn poll_event(&mut self) -> Result<Option<Event>> {
+        Ok(self.events.pop_front())
+    }
+}
@@ -1,26 +1,42 @@
-use unicode_width::UnicodeWidthChar;
-
 use crate::{
     buffer::pixel::Pixel,
     geometry::area::Area,
-    style::{color::Color, color::ColorSystem, style::Style, theme::Theme},
+    style::{color::Color, color::ColorSystem, rgb::RGB, style::Style, theme::Theme},
+    text::{layout::TextLayout, StyledText},
 };
 
 #[derive(Debug, Clone)]
 pub struct Buffer {
     pixels: Vec<Pixel>,
+    /// The position of the top left pixel, not at the origin for a window of a larger
+    /// canvas, see [`Self::window`].
+    x: u16,
+    y: u16,
     width: u16,
     height: u16,
+    /// The color system of the terminal the buffer is drawn on, which tells widgets
+    /// whether to fall back to ASCII glyphs.
+    color_system: ColorSystem,
 }
 
 impl Buffer {
     /// Create a buffer with a specific size.
     pub fn new(width: u16, height: u16) -> Self {
-        let pixels = vec![Pixel::new(); (width * height) as usize];
+        Self::window(Area::from_wh(width, height))
+    }
+
+    /// Create a buffer for an area of a larger canvas, e.g. the visible part of a
+    /// scrolled content. The pixels keep the coordinates of the canvas, and what is
+    /// rendered outside of the area is dropped.
+    pub fn window(area: Area) -> Self {
+        let pixels = vec![Pixel::new(); area.width as usize * area.height as usize];
         Buffer {
             pixels,
-            width,
-            height,
+            x: area.x,
+            y: area.y,
+            width: area.width,
+            height: area.height,
+            color_system: ColorSystem::TrueColor,
         }
     }
 
@@ -34,22 +50,55 @@ impl Buffer {
         self.height
     }
 
+    /// Get the area of the canvas covered by the buffer.
+    pub fn area(&self) -> Area {
+        Area::new(self.x, self.y, self.width, self.height)
+    }
+
+    /// Get the color system of the terminal the buffer is drawn on, `TrueColor` unless
+    /// set by the app.
+    pub fn color_system(&self) -> ColorSystem {
+        self.color_system
+    }
+
+    pub fn set_color_system(&mut self, system: ColorSystem) -> &mut Self {
+        self.color_system = system;
+        self
+    }
+
+    /// Returns whether widgets should only draw ASCII glyphs, i.e. on the legacy
+    /// Windows console.
+    pub fn is_ascii(&self) -> bool {
+        self.color_system == ColorSystem::LegacyWindows
+    }
+
+    /// Get the index of the pixel at (x, y).
+    fn index(&self, x: u16, y: u16) -> usize {
+        (y - self.y) as usize * self.width as usize + (x - self.x) as usize
+    }
+
+    /// Get the pixel at (x, y) from the top left pixel of the buffer.
+    fn pixel(&self, x: u16, y: u16) -> &Pixel {
+        &self.pixels[y as usize * self.width as usize + x as usize]
+    }
+
     /// Get the pixel reference at (x, y).
     /// Panics if the pixel is out of bounds.
     pub fn get(&self, x: u16, y: u16) -> &Pixel {
-        if x >= self.width || y >= self.height {
+        if !self.area().contains(x, y) {
             panic!("Pixel out of buffer bounds: ({}, {})", x, y);
         }
-        &self.pixels[(y * self.width + x) as usize]
+        &self.pixels[self.index(x, y)]
     }
 
     /// Get the mutable pixel reference at (x, y).
     /// Panics if the pixel is out of bounds.
     pub fn get_mut(&mut self, x: u16, y: u16) -> &mut Pixel {
-        if x >= self.width || y >= self.height {
+        if !self.area().contains(x, y) {
             panic!("Pixel out of buffer bounds: ({}, {})", x, y);
         }
-        &mut self.pixels[(y * self.width + x) as usize]
+        let index = self.index(x, y);
+        &mut self.pixels[index]
     }
 
     /// Clear the pixel at (x, y).
@@ -59,28 +108,41 @@ impl Buffer {
         } else if self.get(x, y).is_skip() {
             self.get_mut(x - 1, y).set_character(' ');
         }
-        if self.get(x, y).character().is_some() {
+        if self.get(x, y).grapheme().is_some() || self.get(x, y).is_skip() {
             self.get_mut(x, y).set_character(' ');
         }
         self
     }
 
+    /// Clear the characters of the pixels in an area, e.g. to draw above other widgets.
+    pub fn clear_area(&mut self, area: Area) -> &mut Self {
+        let area = self.area().intersect(area);
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                self.clear_at(x, y);
+            }
+        }
+        self
+    }
+
     /// Directly replace the pixel at (x, y) with a new pixel.
     /// Panics if the pixel is out of bounds.
     /// Warning: This will not process two-width character and skipped pixel.
     fn replace_at(&mut self, x: u16, y: u16, pixel: Pixel) -> &mut Self {
-        if x >= self.width || y >= self.height {
+        if !self.area().contains(x, y) {
             panic!("Pixel out of buffer bounds: ({}, {})", x, y);
         }
-        self.pixels[(y * self.width + x) as usize] = pixel;
+        let index = self.index(x, y);
+        self.pixels[index] = pixel;
         self
     }
 
     /// Render a pixel at (x, y).
     pub fn render_pixel(&mut self, x: u16, y: u16, pixel: &Pixel) -> &mut Self {
-        if y < self.height && x + pixel.width() as u16 <= self.width {
+        let fits = x.saturating_add(pixel.width() as u16) <= self.x + self.width;
+        if self.area().contains(x, y) && fits {
             self.clear_at(x, y);
-            self.get_mut(x, y).render(&pixel);
+            self.get_mut(x, y).render(pixel);
             if pixel.width() == 2 {
                 self.clear_at(x + 1, y);
                 self.get_mut(x + 1, y).set_style(pixel.style()).set_skip();
@@ -96,46 +158,42 @@ impl Buffer {
         area: Area,
         wrap: bool,
     ) -> &mut Self {
-        let area = Area::new(0, 0, self.width, self.height).intersect(area);
-        if area.is_empty() {
+        self.render_spans(&StyledText::from(string.as_ref()), style, area, wrap)
+    }
+
+    /// Render styled text in an area, one line of spans per row. The style of each span
+    /// is patched on `style`. Lines wider than the area are wrapped on word boundaries
+    /// if `wrap` is set, and cut otherwise.
+    pub fn render_spans(
+        &mut self,
+        text: &StyledText,
+        style: Style,
+        area: Area,
+        wrap: bool,
+    ) -> &mut Self {
+        self.render_text(text, style, area, &TextLayout::new().wrap(wrap))
+    }
+
+    /// Render styled text laid out in an area, see [`TextLayout`].
+    pub fn render_text(
+        &mut self,
+        text: &StyledText,
+        style: Style,
+        area: Area,
+        layout: &TextLayout,
+    ) -> &mut Self {
+        // The text is laid out in the whole area, the pixels outside of the buffer are dropped.
+        let visible = self.area().intersect(area);
+        if visible.is_empty() {
             return self;
         }
-        let (start_x, mut y, end_x, end_y) = area.corners();
-
-        let mut render_line = |line: &str, mut y: u16| {
-            let chars = line
-                .chars()
-                .filter(|char| !char.is_control())
-                .map(|cell| (cell, cell.width().unwrap_or(0) as u16))
-                .filter(|(_, width)| *width > 0);
-            let mut x = start_x;
-            for (char, width) in chars {
-                if start_x + width > end_x {
-                    self.render_pixel(start_x, y, Pixel::from_char(' ').set_style(style));
-                    return y + 1; // All lines can not be rendered.
-                }
-                if x + width > end_x {
-                    if end_x - x > 0 {
-                        self.render_pixel(x, y, Pixel::from_char(' ').set_style(style));
-                    }
-                    if wrap && y < end_y {
-                        x = start_x;
-                        y += 1;
-                    } else {
-                        return y + 1;
-                    }
-                }
-                self.render_pixel(x, y, Pixel::from_charw(char, width as u8).set_style(style));
-                x += width;
-            }
-            y + 1
-        };
-
-        let lines = string.as_ref().lines();
-        for line in lines {
-            y = render_line(line, y);
-            if y >= end_y {
-                break;
+        let height = visible.y + visible.height - area.y;
+        let rows = layout.rows(text, style, Some(area.width), Some(height));
+        for (y, row) in rows.into_iter().enumerate() {
+            for glyph in row {
+                let mut pixel = Pixel::from_graphemew(glyph.grapheme, glyph.width as u8);
+                pixel.set_style(glyph.style);
+                self.render_pixel(area.x + glyph.x, area.y + y as u16, &pixel);
             }
         }
         self
@@ -143,11 +201,12 @@ impl Buffer {
 
     /// A cut of an area of buffer
     pub fn cut(&self, area: Area) -> Buffer {
-        let area = Area::new(0, 0, self.width, self.height).intersect(area);
+        let area = self.area().intersect(area);
         if area.is_empty() {
             return Buffer::new(0, 0);
         }
         let mut buffer = Buffer::new(area.width, area.height);
+        buffer.color_system = self.color_system;
         for y in 0..area.height {
             for x in 0..area.width {
                 buffer.replace_at(x, y, self.get(area.x + x, area.y + y).clone());
@@ -167,7 +226,7 @@ impl Buffer {
         if background.is_none() {
             return self;
         }
-        let area = Area::from_wh(self.width, self.height).intersect(area);
+        let area = self.area().intersect(area);
         for y in area.y..area.y + area.height {
             for x in area.x..area.x + area.width {
                 self.get_mut(x, y).set_background(background);
@@ -176,6 +235,26 @@ impl Buffer {
         self
     }
 
+    /// Dim an area as if it were seen through a backdrop: the colors of the pixels keep
+    /// `alpha` of their intensity on the backdrop color, see [`RGB::alpha_on`]. Pixels
+    /// without colors are dimmed from the text and background colors of the theme.
+    pub fn dim(&mut self, area: Area, alpha: u8, backdrop: RGB, theme: &Theme) -> &mut Self {
+        let area = self.area().intersect(area);
+        let dim = |color: Option<Color>, default: Color| {
+            let mut color = color.unwrap_or(default).on_theme(theme);
+            color.alpha_on(alpha, backdrop);
+            Some(Color::RGB(color))
+        };
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                let style = self.get_mut(x, y).style_mut();
+                style.foreground = dim(style.foreground, Color::Text);
+                style.background = dim(style.background, Color::Background);
+            }
+        }
+        self
+    }
+
     pub fn clear(&mut self) -> &mut Self {
         for pixel in self.pixels.iter_mut() {
             pixel.clear();
@@ -190,16 +269,14 @@ impl Buffer {
 
         for y in 0..self.height {
             for x in 0..self.width {
-                let pixel = self.get(x, y);
+                let pixel = self.pixel(x, y);
                 if pixel.style() != last_style {
                     out.push_str(&ansi(&pixel.style()));
                     last_style = pixel.style();
                 }
-                if let Some(character) = pixel.character() {
-                    if character as u32 != 0 {
-                        out.push(character);
-                    }
-                } else {
+                if let Some(grapheme) = pixel.grapheme() {
+                    out.push_str(grapheme);
+                } else if !pixel.is_skip() {
                     out.push(' ');
                 }
             }
@@ -210,24 +287,132 @@ impl Buffer {
         out
     }
 
+    /// Get the characters of the buffer as plain text, one line per row, without the
+    /// trailing spaces. Useful to assert on a frame in tests.
+    pub fn text(&self) -> String {
+        (0..self.height)
+            .map(|y| self.line(y).trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Get a readable snapshot of the characters and styles of the buffer. Each row is
+    /// followed by its runs of styled pixels, one per line, e.g.
+    /// `  0..5 fg=Text bg=Background attrs=1`. Unstyled runs are omitted.
+    pub fn styled_text(&self) -> String {
+        let mut out = Vec::new();
+        for y in 0..self.height {
+            out.push(self.line(y).trim_end().to_string());
+            let mut start = 0;
+            while start < self.width {
+                let style = self.pixel(start, y).style();
+                let mut end = start + 1;
+                while end < self.width && self.pixel(end, y).style() == style {
+                    end += 1;
+                }
+                if style != Style::new() {
+                    let mut run = format!("  {}..{}", start, end);
+                    if let Some(foreground) = style.foreground {
+                        run.push_str(&format!(" fg={:?}", foreground));
+                    }
+                    if let Some(background) = style.background {
+                        run.push_str(&format!(" bg={:?}", background));
+                    }
+                    if !style.attributes.is_empty() {
+                        run.push_str(&format!(" attrs={}", style.attributes.ansi_codes()));
+                    }
+                    out.push(run);
+                }
+                start = end;
+            }
+        }
+        out.join("\n")
+    }
+
+    /// Get the graphemes of a row, with empty pixels as spaces.
+    fn line(&self, y: u16) -> String {
+        (0..self.width)
+            .map(|x| self.pixel(x, y))
+            .filter(|pixel| !pixel.is_skip())
+            .map(|pixel| pixel.grapheme().unwrap_or(" "))
+            .collect()
+    }
+
+    /// Get the ANSI output that turns the `previous` frame into this one.
+    /// Only changed pixels are written, with cursor moves between the changed runs
+    /// and style sequences emitted only for the attributes which change.
+    /// If the sizes differ, every pixel is considered changed.
+    pub fn view_diff(&self, previous: &Buffer, system: ColorSystem, theme: &Theme) -> String {
+        let mut out = String::new();
+        let resized = self.width != previous.width || self.height != previous.height;
+        let changed = |x: u16, y: u16| resized || self.pixel(x, y) != previous.pixel(x, y);
+        let mut last_style = None;
+        let mut cursor = None;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let pixel = self.pixel(x, y);
+                // Skipped pixels are covered by the two-width character before them.
+                if pixel.is_skip() {
+                    continue;
+                }
+                let dirty = changed(x, y)
+                    || (pixel.width() == 2 && x + 1 < self.width && changed(x + 1, y));
+                if !dirty {
+                    continue;
+                }
+                if cursor != Some((x, y)) {
+                    out.push_str(&format!("\x1b[{};{}H", y + 1, x + 1));
+                }
+                // The first style resets the terminal, the next ones only change what
+                // differs from the previous style.
+                match last_style {
+                    None => out.push_str(&format!(
+                        "\x1b[0;{}m",
+                        pixel.style().ansi_codes(system, theme)
+                    )),
+                    Some(last) if last != pixel.style() => {
+                        let codes = pixel.style().ansi_codes_from(&last, system, theme);
+                        // An empty sequence would reset the style.
+                        if !codes.is_empty() {
+                            out.push_str(&format!("\x1b[{}m", codes));
+                        }
+                    }
+                    Some(_) => {}
+                }
+                last_style = Some(pixel.style());
+                out.push_str(pixel.grapheme().unwrap_or(" "));
+                cursor = Some((x + pixel.width() as u16, y));
+            }
+        }
+
+        if last_style.is_some() {
+            out.push_str("\x1b[0m");
+        }
+        out
+    }
+
     #[allow(unused_assignments)]
     pub fn render(&mut self, x: u16, y: u16, buffer: &Buffer) -> &mut Self {
+        if !self.area().contains(x, y) {
+            return self;
+        }
         let start_x = x;
         let start_y = y;
-        let width = buffer.width.min(self.width - x);
-        let height = buffer.height.min(self.height - y);
+        let width = buffer.width.min(self.x + self.width - x);
+        let height = buffer.height.min(self.y + self.height - y);
         for y in start_y..start_y + height {
             self.clear_at(x, y);
             let mut x = start_x;
             while x < start_x + width {
-                let pixel = buffer.get(x - start_x, y - start_y);
-                if pixel.width() as u16 + x >= start_x + width {
+                let pixel = buffer.pixel(x - start_x, y - start_y);
+                if pixel.width() as u16 + x > start_x + width {
                     break;
                 }
                 self.render_pixel(x, y, pixel);
-                x += buffer.get(x - start_x, y - start_y).width() as u16;
+                x += (pixel.width() as u16).max(1);
             }
-            if x < self.width && self.get(x, y).is_skip() {
+            if x < self.x + self.width && self.get(x, y).is_skip() {
                 self.get_mut(x, y).clear_char();
             }
         }
@@ -235,7 +420,7 @@ impl Buffer {
     }
 
     pub fn fill(&mut self, area: Area, pixel: Pixel) -> &mut Self {
-        let area = Area::new(0, 0, self.width, self.height).intersect(area);
+        let area = self.area().intersect(area);
         for y in area.y..area.y + area.height {
             let mut x = area.x;
             while x < area.x + area.width {
@@ -245,7 +430,7 @@ impl Buffer {
                 self.render_pixel(x, y, &pixel);
                 x += pixel.width() as u16;
             }
-            if x < self.width && self.get(x, y).is_skip() {
+            if x < self.x + self.width && self.get(x, y).is_skip() {
                 self.get_mut(x, y).clear_char();
             }
         }
@@ -259,6 +444,7 @@ mod tests {
     use crate::{
         geometry::area::Area,
         style::{color::Color, color::ColorSystem, style::Style, theme::Theme},
+        text::{Line, Span, StyledText, Text},
     };
 
     use super::Buffer;
@@ -275,20 +461,11 @@ mod tests {
             )
             .render_string("你好，世界！", Style::new(), Area::new(1, 0, 19, 15), false)
             .render_string("你好，世界！", Style::new(), Area::new(19, 0, 1, 15), false);
-        for y in 0..15 {
-            for x in 0..20 {
-                let pixel = buffer.get(x, y);
-                print!(
-                    "{}",
-                    if pixel.character().is_some() {
-                        pixel.character().unwrap()
-                    } else {
-                        '-'
-                    }
-                );
-            }
-            println!();
-        }
+        // The first character is cut in half by the second string, and the last one does
+        // not fit in a column.
+        let mut expected = " 你好，世界！\n你好".to_string();
+        expected.push_str(&"\n".repeat(13));
+        assert_eq!(buffer.text(), expected);
     }
 
     #[test]
@@ -301,20 +478,51 @@ mod tests {
             false,
         );
         let buffer = buffer.cut(Area::new(1, 0, 18, 2));
-        for y in 0..2 {
-            for x in 0..18 {
-                let pixel = buffer.get(x, y);
-                print!(
-                    "{}",
-                    if pixel.character().is_some() {
-                        pixel.character().unwrap()
-                    } else {
-                        '-'
-                    }
-                );
-            }
-            println!();
-        }
+        // The wide characters cut in half are dropped.
+        assert_eq!(buffer.text(), " 好，世界！你好，\n 好，世界！");
+    }
+
+    #[test]
+    fn view_diff() {
+        let theme = Theme::TOKYO_NIGHT;
+        let previous = Buffer::new(10, 2);
+        let mut buffer = previous.clone();
+        assert_eq!(
+            buffer.view_diff(&previous, ColorSystem::TrueColor, &theme),
+            ""
+        );
+
+        buffer.render_string("ab", Style::new(), Area::new(2, 1, 8, 1), false);
+        assert_eq!(
+            buffer.view_diff(&previous, ColorSystem::TrueColor, &theme),
+            "\x1b[2;3H\x1b[0;mab\x1b[0m"
+        );
+
+        let previous = buffer.clone();
+        buffer
+            .render_string("你", Style::new().bold(), Area::new(0, 0, 10, 1), false)
+            .render_string("c", Style::new(), Area::new(3, 1, 7, 1), false);
+        assert_eq!(
+            buffer.view_diff(&previous, ColorSystem::TrueColor, &theme),
+            "\x1b[1;1H\x1b[0;1m你\x1b[2;4H\x1b[22mc\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn render() {
+        let mut source = Buffer::new(4, 1);
+        source.render_string("a你b", Style::new(), Area::new(0, 0, 4, 1), false);
+        let mut buffer = Buffer::new(6, 2);
+        buffer.render(2, 1, &source).render(5, 0, &source);
+        let rows: Vec<String> = (0..2)
+            .map(|y| {
+                (0..6)
+                    .filter(|x| !buffer.get(*x, y).is_skip())
+                    .map(|x| buffer.get(x, y).grapheme().unwrap_or("-"))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(rows, vec!["-----a", "--a你b"]);
     }
 
     #[test]
@@ -329,6 +537,74 @@ mod tests {
             )
             .render_background(Area::from_wh(20, 15), Some(Color::Background))
             .view(ColorSystem::TrueColor, &Theme::TOKYO_NIGHT);
-        print!("{}", out);
+        assert!(out.starts_with("\x1b[38;2;"));
+        assert_eq!(out.matches("\x1b[0m").count(), 15);
+    }
+
+    #[test]
+    fn render_spans() {
+        let mut buffer = Buffer::new(5, 2);
+        let text = StyledText::from(Line::from("a你").push(Span::styled(
+            "好b",
+            Style::new().foreground(Color::Primary).bold(),
+        )));
+        buffer.render_spans(
+            &text,
+            Style::new().foreground(Color::Text),
+            Area::from_wh(5, 2),
+            true,
+        );
+        assert_eq!(buffer.text(), "a你好\nb");
+        assert_eq!(
+            buffer.styled_text(),
+            "a你好\n  0..3 fg=Text\n  3..5 fg=Primary attrs=1\nb\n  0..1 fg=Primary attrs=1"
+        );
+    }
+
+    #[test]
+    fn graphemes() {
+        let mut buffer = Buffer::new(11, 1);
+        let text = "e\u{301}🇫🇷👨\u{200d}👩\u{200d}👧❤\u{fe0f}x";
+        buffer.render_string(text, Style::new(), Area::from_wh(11, 1), false);
+        let graphemes: Vec<_> = (0..11)
+            .filter(|x| !buffer.get(*x, 0).is_skip())
+            .map(|x| buffer.get(x, 0).grapheme().unwrap_or("-"))
+            .collect();
+        assert_eq!(
+            graphemes,
+            vec![
+                "e\u{301}",
+                "🇫🇷",
+                "👨\u{200d}👩\u{200d}👧",
+                "❤\u{fe0f}",
+                "x",
+                "-",
+                "-",
+                "-"
+            ]
+        );
+        assert_eq!(buffer.text(), text);
+        assert_eq!(Text::size_of(text).width, 8);
+        assert!(buffer
+            .view(ColorSystem::TrueColor, &Theme::TOKYO_NIGHT)
+            .contains(text));
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut buffer = Buffer::new(8, 3);
+        buffer
+            .render_string("ab 你", Style::new().bold(), Area::new(1, 0, 7, 1), false)
+            .render_string(
+                "c",
+                Style::new().foreground(Color::Primary),
+                Area::new(0, 1, 8, 1),
+                false,
+            );
+        assert_eq!(buffer.text(), " ab 你\nc\n");
+        assert_eq!(
+            buffer.styled_text(),
+            " ab 你\n  1..6 attrs=1\nc\n  0..1 fg=Primary\n"
+        );
     }
 }
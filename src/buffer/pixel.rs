@@ -1,19 +1,65 @@
 use unicode_width::UnicodeWidthChar;
 
-use crate::style::{color::Color, style::Style};
+use crate::{
+    style::{color::Color, style::Style},
+    text::grapheme_width,
+};
+
+/// The number of bytes of a grapheme stored without allocation.
+const INLINE: usize = 14;
+
+/// The grapheme cluster displayed by a pixel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Symbol {
+    /// A short grapheme, e.g. a single character, an accented letter or a flag.
+    Inline { bytes: [u8; INLINE], len: u8 },
+    /// A long grapheme, e.g. an emoji ZWJ sequence.
+    Heap(Box<str>),
+    /// Covered by the two-width grapheme before, not rendered.
+    Skip,
+}
+
+impl Symbol {
+    fn new(grapheme: &str) -> Self {
+        if grapheme.len() <= INLINE {
+            let mut bytes = [0; INLINE];
+            bytes[..grapheme.len()].copy_from_slice(grapheme.as_bytes());
+            Symbol::Inline {
+                bytes,
+                len: grapheme.len() as u8,
+            }
+        } else {
+            Symbol::Heap(grapheme.into())
+        }
+    }
 
-#[derive(Debug, Clone)]
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Symbol::Inline { bytes, len } => std::str::from_utf8(&bytes[..*len as usize]).ok(),
+            Symbol::Heap(grapheme) => Some(grapheme),
+            Symbol::Skip => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Pixel {
-    character: Option<char>,
+    symbol: Option<Symbol>,
     width: u8,
     style: Style,
 }
 
+impl Default for Pixel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Pixel {
     /// Create an empty pixel.
     pub const fn new() -> Self {
         Pixel {
-            character: None,
+            symbol: None,
             width: 1,
             style: Style::new(),
         }
@@ -22,29 +68,44 @@ impl Pixel {
     /// Create a pixel with a character.
     pub fn from_char(character: char) -> Self {
         let width = character.width().unwrap() as u8;
-        Pixel {
-            character: Some(character),
-            width,
-            style: Style::new(),
-        }
+        Self::from_charw(character, width)
     }
 
     /// Create a pixel with a character and set the width.
     /// Ensure the width is correct, otherwise the rendering will be incorrect.
     pub fn from_charw(character: char, width: u8) -> Self {
+        Self::from_graphemew(character.encode_utf8(&mut [0; 4]), width)
+    }
+
+    /// Create a pixel with a grapheme cluster, e.g. `"e\u{301}"` or `"🇫🇷"`.
+    /// Graphemes are displayed in one or two columns, see [`grapheme_width`].
+    pub fn from_grapheme(grapheme: &str) -> Self {
+        let width = grapheme_width(grapheme).clamp(1, 2) as u8;
+        Self::from_graphemew(grapheme, width)
+    }
+
+    /// Create a pixel with a grapheme cluster and set the width.
+    /// Ensure the width is correct, otherwise the rendering will be incorrect.
+    pub fn from_graphemew(grapheme: &str, width: u8) -> Self {
         Pixel {
-            character: Some(character),
+            symbol: Some(Symbol::new(grapheme)),
             width,
             style: Style::new(),
         }
     }
 
     pub fn set_character(&mut self, character: char) -> &Self {
-        self.character = Some(character);
+        self.symbol = Some(Symbol::new(character.encode_utf8(&mut [0; 4])));
         self.width = character.width().unwrap() as u8;
         self
     }
 
+    pub fn set_grapheme(&mut self, grapheme: &str) -> &Self {
+        self.symbol = Some(Symbol::new(grapheme));
+        self.width = grapheme_width(grapheme).clamp(1, 2) as u8;
+        self
+    }
+
     pub fn width(&self) -> u8 {
         self.width
     }
@@ -61,7 +122,7 @@ impl Pixel {
 
     /// Clear the character and style.
     pub fn clear(&mut self) -> &mut Self {
-        self.character = None;
+        self.symbol = None;
         self.width = 1;
         self.style = Style::new();
         self
@@ -69,7 +130,7 @@ impl Pixel {
 
     /// Clear the character only.
     pub fn clear_char(&mut self) -> &mut Self {
-        self.character = None;
+        self.symbol = None;
         self.width = 1;
         self
     }
@@ -77,23 +138,23 @@ impl Pixel {
     /// Set the pixel as skipped, the pixel will not be rendered.
     /// This is usually used after a two-width character.
     pub fn set_skip(&mut self) -> &mut Self {
-        self.character = Some(char::from_u32(0).unwrap());
+        self.symbol = Some(Symbol::Skip);
         self.width = 0;
         self
     }
 
     pub fn is_skip(&self) -> bool {
-        self.character.is_some() && self.character.unwrap() as u32 == 0
+        self.symbol == Some(Symbol::Skip)
     }
 
     /// Render another pixel on this pixel.
     pub fn render(&mut self, pixel: &Pixel) -> &mut Self {
         if pixel.style.background.is_some() {
-            self.character = pixel.character;
+            self.symbol = pixel.symbol.clone();
             self.width = pixel.width;
-            self.style = pixel.style.clone();
-        } else if pixel.character.is_some() {
-            self.character = pixel.character;
+            self.style = pixel.style;
+        } else if pixel.symbol.is_some() {
+            self.symbol = pixel.symbol.clone();
             self.width = pixel.width;
             self.style.foreground = pixel.style.foreground;
             self.style.attributes = pixel.style.attributes;
@@ -101,12 +162,18 @@ impl Pixel {
         self
     }
 
+    /// Get the grapheme of the pixel, `None` if the pixel is empty or skipped.
+    pub fn grapheme(&self) -> Option<&str> {
+        self.symbol.as_ref().and_then(Symbol::as_str)
+    }
+
+    /// Get the first character of the grapheme, see [`Self::grapheme`].
     pub fn character(&self) -> Option<char> {
-        self.character
+        self.grapheme().and_then(|grapheme| grapheme.chars().next())
     }
 
     pub fn style(&self) -> Style {
-        self.style.clone()
+        self.style
     }
 
     pub fn style_ref(&self) -> &Style {
@@ -0,0 +1,131 @@
+use std::{
+    future::Future,
+    pin::{pin, Pin},
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+/// A background action of a [`Command`].
+enum Action<Message> {
+    Perform(Box<dyn FnOnce() -> Message + Send>),
+    Future(Pin<Box<dyn Future<Output = Message> + Send>>),
+}
+
+/// Work to be done off the main loop, returned by `Program::update`.
+/// Each action runs on its own thread, and the message it produces is given back to
+/// `Program::update`.
+pub struct Command<Message> {
+    actions: Vec<Action<Message>>,
+}
+
+impl<Message> Command<Message> {
+    /// A command doing nothing.
+    pub const fn none() -> Self {
+        Self {
+            actions: Vec::new(),
+        }
+    }
+
+    /// Run several commands at once.
+    pub fn batch(commands: impl IntoIterator<Item = Command<Message>>) -> Self {
+        Self {
+            actions: commands
+                .into_iter()
+                .flat_map(|command| command.actions)
+                .collect(),
+        }
+    }
+
+    /// Run a blocking task, e.g. a file read or a subprocess, on a thread and map its
+    /// result into a message.
+    pub fn perform<T>(
+        task: impl FnOnce() -> T + Send + 'static,
+        map: impl FnOnce(T) -> Message + Send + 'static,
+    ) -> Self {
+        Self {
+            actions: vec![Action::Perform(Box::new(move || map(task())))],
+        }
+    }
+
+    /// Run a future to completion on a thread, producing a message.
+    pub fn future(future: impl Future<Output = Message> + Send + 'static) -> Self {
+        Self {
+            actions: vec![Action::Future(Box::pin(future))],
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Spawn a thread for each action, and call `send` with the produced messages.
+    pub(crate) fn execute(self, send: impl Fn(Message) + Clone + Send + 'static)
+    where
+        Message: 'static,
+    {
+        for action in self.actions {
+            let send = send.clone();
+            thread::spawn(move || {
+                let message = match action {
+                    Action::Perform(task) => task(),
+                    Action::Future(future) => block_on(future),
+                };
+                send(message);
+            });
+        }
+    }
+}
+
+impl<Message> Default for Command<Message> {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Wakes a thread parked by [`block_on`].
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Poll a future on the current thread until it completes, parking the thread while
+/// the future is pending.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, time::Duration};
+
+    use super::Command;
+
+    #[test]
+    fn execute() {
+        let command = Command::batch([
+            Command::perform(|| 1 + 1, |value| value * 10),
+            Command::future(async { 3 }),
+            Command::none(),
+        ]);
+        let (sender, receiver) = mpsc::channel();
+        command.execute(move |message| sender.send(message).unwrap());
+
+        let mut messages: Vec<i32> = (0..2)
+            .map(|_| receiver.recv_timeout(Duration::from_secs(1)).unwrap())
+            .collect();
+        messages.sort();
+        assert_eq!(messages, vec![3, 20]);
+    }
+}
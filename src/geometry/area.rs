@@ -80,6 +80,19 @@ impl Area {
     pub fn contains(&self, x: u16, y: u16) -> bool {
         x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
     }
+
+    /// Moves the area by an offset. The parts moved to negative coordinates are cut.
+    pub fn translate(&self, dx: i32, dy: i32) -> Self {
+        let clamp = |value: i32| value.clamp(0, u16::MAX as i32) as u16;
+        let (x1, y1) = (self.x as i32 + dx, self.y as i32 + dy);
+        let (x2, y2) = (x1 + self.width as i32, y1 + self.height as i32);
+        Area::new(
+            clamp(x1),
+            clamp(y1),
+            clamp(x2) - clamp(x1),
+            clamp(y2) - clamp(y1),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -44,11 +44,12 @@ impl Length {
         let mut shrink_count = 0;
 
         // First pass: handle Fixed and count Preferred/Fill/Part/Shrink
-        for (length, preference) in lengths.iter().zip(preferences.into_iter()) {
+        for (length, preference) in lengths.iter().zip(preferences) {
             match length {
                 Length::Preferred => {
-                    result.push(preference);
-                    remaining -= preference;
+                    let size = preference.min(remaining);
+                    result.push(size);
+                    remaining -= size;
                 }
                 Length::Fixed(size) => {
                     if *size <= remaining {
@@ -76,8 +77,7 @@ impl Length {
 
         // Second pass: handle Fill
         let mut last = None;
-        if fill_count > 0 {
-            let fill_length = remaining / fill_count;
+        if let Some(fill_length) = remaining.checked_div(fill_count) {
             for (i, length) in lengths.iter().enumerate() {
                 if matches!(length, Length::Fill) {
                     last = Some(i);
@@ -94,19 +94,17 @@ impl Length {
         }
 
         // Third pass: handle Part
-        if part_sum > 0 {
-            for (i, length) in lengths.iter().enumerate() {
-                if let Length::Part(weight) = length {
-                    result[i] = (remaining * weight) / part_sum;
-                }
+        for (i, length) in lengths.iter().enumerate() {
+            if let Length::Part(weight) = length {
+                result[i] = (remaining * weight).checked_div(part_sum).unwrap_or(0);
             }
         }
 
         // Final pass: handle Shrink
-        if shrink_count > 0 {
+        if let Some(shrink_length) = remaining.checked_div(shrink_count) {
             for (i, length) in lengths.iter().enumerate() {
                 if matches!(length, Length::Shrink) {
-                    result[i] = remaining / shrink_count;
+                    result[i] = shrink_length;
                 }
             }
         }
@@ -37,8 +37,8 @@ impl<T> From<(T, T)> for Size<T> {
     }
 }
 
-impl<T> Into<(T, T)> for Size<T> {
-    fn into(self) -> (T, T) {
-        (self.width, self.height)
+impl<T> From<Size<T>> for (T, T) {
+    fn from(value: Size<T>) -> Self {
+        (value.width, value.height)
     }
 }
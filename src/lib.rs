@@ -1,8 +1,13 @@
+#![allow(clippy::module_inception)]
+
 pub mod style;
 pub mod geometry;
 pub mod buffer;
+pub mod backend;
 pub mod terminal;
 pub mod shell;
+pub mod command;
+pub mod subscription;
 pub mod text;
 pub mod widget;
 pub mod widgets;
\ No newline at end of file
@@ -3,6 +3,13 @@
 pub struct Shell<Message> {
     messages: Vec<Message>,
     event_captured: bool,
+    focus_requested: bool,
+}
+
+impl<Message> Default for Shell<Message> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<Message> Shell<Message> {
@@ -10,6 +17,7 @@ impl<Message> Shell<Message> {
         Self {
             messages: Vec::new(),
             event_captured: false,
+            focus_requested: false,
         }
     }
 
@@ -21,6 +29,22 @@ impl<Message> Shell<Message> {
         self.event_captured
     }
 
+    /// Capture the current event, so that it will not be propagated to other widgets
+    /// or processed by the app.
+    pub fn capture_event(&mut self) {
+        self.event_captured = true;
+    }
+
+    /// Request the keyboard focus for the widget processing the event.
+    /// The widget should focus itself, so that the app can find it in the focus chain.
+    pub fn request_focus(&mut self) {
+        self.focus_requested = true;
+    }
+
+    pub fn is_focus_requested(&self) -> bool {
+        self.focus_requested
+    }
+
     pub fn messages(&self) -> &Vec<Message> {
         &self.messages
     }
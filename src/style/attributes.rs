@@ -3,12 +3,38 @@ pub struct Attributes {
     value: u16,
 }
 
+impl Default for Attributes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Attributes {
     /// Create an empty set of attributes.
     pub const fn new() -> Self {
         Attributes { value: 0 }
     }
 
+    /// Get a single attribute by its name, e.g. `"bold"`.
+    pub fn from_name(name: &str) -> Option<Attributes> {
+        let mut attributes = Attributes::new();
+        let attributes = match name {
+            "bold" => attributes.bold(),
+            "dim" => attributes.dim(),
+            "italic" => attributes.italic(),
+            "underline" => attributes.underline(),
+            "blink" => attributes.blink(),
+            "reverse" => attributes.reverse(),
+            "conceal" => attributes.conceal(),
+            "strike" => attributes.strike(),
+            "frame" => attributes.frame(),
+            "encircle" => attributes.encircle(),
+            "overline" => attributes.overline(),
+            _ => return None,
+        };
+        Some(attributes)
+    }
+
     pub fn clear(&mut self) -> Self {
         self.value = 0;
         *self
@@ -69,6 +95,13 @@ impl Attributes {
         *self
     }
 
+    /// Get the attributes set in either `self` or `other`.
+    pub fn union(self, other: Attributes) -> Self {
+        Attributes {
+            value: self.value | other.value,
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.value == 0
     }
@@ -110,6 +143,37 @@ impl Attributes {
         }
         codes.join(";")
     }
+
+    /// Get the ANSI codes turning `previous` into these attributes, without a reset.
+    pub fn ansi_codes_from(&self, previous: Attributes) -> String {
+        // The codes turning attributes off, some of them turn two attributes off.
+        const OFF: [(u16, &str); 9] = [
+            (1 | 2, "22"),
+            (4, "23"),
+            (8, "24"),
+            (16, "25"),
+            (32, "27"),
+            (64, "28"),
+            (128, "29"),
+            (256 | 512, "54"),
+            (1024, "55"),
+        ];
+        let removed = previous.value & !self.value;
+        let mut added = self.value & !previous.value;
+        let mut codes = Vec::new();
+        for (mask, code) in OFF {
+            if removed & mask != 0 {
+                codes.push(code.to_string());
+                // Turn back on what the code also turned off.
+                added |= self.value & mask;
+            }
+        }
+        let added = Attributes { value: added };
+        if !added.is_empty() {
+            codes.push(added.ansi_codes());
+        }
+        codes.join(";")
+    }
 }
 
 #[cfg(test)]
@@ -120,5 +184,12 @@ mod tests {
     fn ansi_codes() {
         let attributes = Attributes::new().italic().strike().underline();
         println!("\x1b[{}mHello, world!\x1b[0m", attributes.ansi_codes());
+
+        let bold_dim = Attributes::new().bold().dim();
+        assert_eq!(Attributes::new().dim().ansi_codes_from(bold_dim), "22;2");
+        assert_eq!(
+            attributes.ansi_codes_from(Attributes::new().italic()),
+            "4;9"
+        );
     }
 }
@@ -17,10 +17,33 @@ pub enum Color {
     Success,
     Warning,
     Danger,
+    Focus,
+    Disabled,
+    Border,
+    Stripe,
+    Selection,
     RGB(RGB),
 }
 
 impl Color {
+    /// Get a theme color by its name, e.g. `"primary"`.
+    pub fn from_name(name: &str) -> Option<Color> {
+        match name {
+            "text" => Some(Color::Text),
+            "background" => Some(Color::Background),
+            "primary" => Some(Color::Primary),
+            "success" => Some(Color::Success),
+            "warning" => Some(Color::Warning),
+            "danger" => Some(Color::Danger),
+            "focus" => Some(Color::Focus),
+            "disabled" => Some(Color::Disabled),
+            "border" => Some(Color::Border),
+            "stripe" => Some(Color::Stripe),
+            "selection" => Some(Color::Selection),
+            _ => None,
+        }
+    }
+
     pub fn on_theme(&self, theme: &Theme) -> RGB {
         match *self {
             Color::Text => theme.text,
@@ -29,6 +52,11 @@ impl Color {
             Color::Success => theme.success,
             Color::Warning => theme.warning,
             Color::Danger => theme.danger,
+            Color::Focus => theme.focus,
+            Color::Disabled => theme.disabled,
+            Color::Border => theme.border,
+            Color::Stripe => theme.stripe,
+            Color::Selection => theme.selection,
             Color::RGB(rgb) => rgb,
         }
     }
@@ -53,15 +81,8 @@ impl From<(u8, u8, u8)> for Color {
 }
 
 impl From<&str> for Color {
+    /// Falls back to [`Color::Text`] for unknown names, see [`Color::from_name`].
     fn from(name: &str) -> Self {
-        match name {
-            "text" => Color::Text,
-            "background" => Color::Background,
-            "primary" => Color::Primary,
-            "success" => Color::Success,
-            "warning" => Color::Warning,
-            "danger" => Color::Danger,
-            _ => Color::Text,
-        }
+        Color::from_name(name).unwrap_or(Color::Text)
     }
 }
@@ -7,6 +7,12 @@ pub struct Style {
     pub attributes: Attributes,
 }
 
+impl Default for Style {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Style {
     /// Create an empty style.
     pub const fn new() -> Self {
@@ -61,6 +67,15 @@ impl Style {
         self
     }
 
+    /// Apply another style on this one: the colors set in `other` replace these, and
+    /// its attributes are added.
+    pub fn patch(mut self, other: Style) -> Self {
+        self.foreground = other.foreground.or(self.foreground);
+        self.background = other.background.or(self.background);
+        self.attributes = self.attributes.union(other.attributes);
+        self
+    }
+
     pub fn bold(mut self) -> Self {
         self.attributes.bold();
         self
@@ -131,6 +146,31 @@ impl Style {
         }
         codes.join(";")
     }
+
+    /// Get the ANSI codes turning the `previous` style into this one, only for the
+    /// colors and attributes which differ.
+    pub fn ansi_codes_from(&self, previous: &Style, system: ColorSystem, theme: &Theme) -> String {
+        let mut codes = Vec::new();
+        if system != ColorSystem::Disabled {
+            if self.foreground != previous.foreground {
+                codes.push(match self.foreground {
+                    Some(foreground) => foreground.on_theme(theme).ansi_codes(system, true),
+                    None => "39".to_string(),
+                });
+            }
+            if self.background != previous.background {
+                codes.push(match self.background {
+                    Some(background) => background.on_theme(theme).ansi_codes(system, false),
+                    None => "49".to_string(),
+                });
+            }
+        }
+        let attributes = self.attributes.ansi_codes_from(previous.attributes);
+        if !attributes.is_empty() {
+            codes.push(attributes);
+        }
+        codes.join(";")
+    }
 }
 
 #[cfg(test)]
@@ -149,5 +189,12 @@ mod tests {
             "\x1b[{}mHello, world!\x1b[0m",
             style.ansi_codes(ColorSystem::TrueColor, &Theme::TOKYO_NIGHT)
         );
+
+        // Only what differs is changed.
+        let plain = Style::new().background(Color::Background);
+        assert_eq!(
+            plain.ansi_codes_from(&style, ColorSystem::TrueColor, &Theme::TOKYO_NIGHT),
+            "39;29"
+        );
     }
 }
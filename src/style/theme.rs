@@ -8,6 +8,13 @@ pub struct Theme {
     pub success: RGB,
     pub warning: RGB,
     pub danger: RGB,
+    pub focus: RGB,
+    pub disabled: RGB,
+    pub border: RGB,
+    /// The background of every other row of tables and lists.
+    pub stripe: RGB,
+    /// The background of selected items.
+    pub selection: RGB,
 }
 
 impl Theme {
@@ -18,5 +25,10 @@ impl Theme {
         success: RGB::from_hex(0x9ECE6A),
         warning: RGB::from_hex(0xE0AF68),
         danger: RGB::from_hex(0xF7768E),
+        focus: RGB::from_hex(0x7AA2F7),
+        disabled: RGB::from_hex(0x565F89),
+        border: RGB::from_hex(0x3B4261),
+        stripe: RGB::from_hex(0x1F2335),
+        selection: RGB::from_hex(0x283457),
     };
 }
@@ -0,0 +1,252 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{Receiver, RecvTimeoutError},
+        Arc, Mutex,
+    },
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+/// How long a channel worker waits before checking whether it has been stopped.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Gives a message produced by a worker to the app.
+type Emit<Message> = Box<dyn Fn(Message) + Send>;
+type Worker<Message> = Box<dyn FnOnce(Arc<AtomicBool>, Emit<Message>) + Send>;
+
+enum Kind<Message> {
+    /// Runs on its own thread until the flag is cleared.
+    Worker(Worker<Message>),
+    /// Maps the terminal resize events, handled by the app itself.
+    Resize(Box<dyn Fn(u16, u16) -> Message>),
+}
+
+struct Recipe<Message> {
+    id: u64,
+    kind: Kind<Message>,
+}
+
+/// A declarative source of messages, returned by `Program::subscription`.
+///
+/// The app asks for the subscription on every frame. A subscription is started the
+/// first time it appears and keeps running, without being restarted, as long as a
+/// subscription with the same identity is returned. It is stopped once it is no
+/// longer returned.
+pub struct Subscription<Message> {
+    recipes: Vec<Recipe<Message>>,
+}
+
+fn id(kind: &str, key: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    kind.hash(&mut hasher);
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<Message> Subscription<Message> {
+    /// A subscription producing nothing.
+    pub const fn none() -> Self {
+        Self {
+            recipes: Vec::new(),
+        }
+    }
+
+    /// Subscribe to several subscriptions at once.
+    pub fn batch(subscriptions: impl IntoIterator<Item = Subscription<Message>>) -> Self {
+        Self {
+            recipes: subscriptions
+                .into_iter()
+                .flat_map(|subscription| subscription.recipes)
+                .collect(),
+        }
+    }
+
+    /// Produce a message every `period`, with the time of the tick.
+    /// Identified by `key` and the period, so that two timers with the same period
+    /// both run.
+    pub fn every(
+        key: impl Hash,
+        period: Duration,
+        map: impl Fn(Instant) -> Message + Send + 'static,
+    ) -> Self {
+        let worker = move |running: Arc<AtomicBool>, emit: Emit<Message>| {
+            let mut next = Instant::now() + period;
+            while running.load(Ordering::Relaxed) {
+                let now = Instant::now();
+                if now < next {
+                    thread::park_timeout(next - now);
+                    continue;
+                }
+                emit(map(now));
+                next = (next + period).max(now);
+            }
+        };
+        Self {
+            recipes: vec![Recipe {
+                id: id("every", (key, period)),
+                kind: Kind::Worker(Box::new(worker)),
+            }],
+        }
+    }
+
+    /// Produce a message when the terminal is resized, with the new width and height.
+    pub fn resize(map: impl Fn(u16, u16) -> Message + 'static) -> Self {
+        Self {
+            recipes: vec![Recipe {
+                id: id("resize", ()),
+                kind: Kind::Resize(Box::new(map)),
+            }],
+        }
+    }
+
+    /// Produce a message for each value received from an external channel.
+    /// Identified by `key`, the receiver is shared so that the program can return the
+    /// same receiver on every frame.
+    pub fn channel<T: Send + 'static>(
+        key: impl Hash,
+        receiver: Arc<Mutex<Receiver<T>>>,
+        map: impl Fn(T) -> Message + Send + 'static,
+    ) -> Self {
+        let worker = move |running: Arc<AtomicBool>, emit: Emit<Message>| {
+            while running.load(Ordering::Relaxed) {
+                let received = receiver.lock().unwrap().recv_timeout(POLL_INTERVAL);
+                match received {
+                    Ok(value) => emit(map(value)),
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        };
+        Self {
+            recipes: vec![Recipe {
+                id: id("channel", key),
+                kind: Kind::Worker(Box::new(worker)),
+            }],
+        }
+    }
+}
+
+impl<Message> Default for Subscription<Message> {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// A running worker of a subscription.
+struct Handle {
+    running: Arc<AtomicBool>,
+    thread: Thread,
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        self.thread.unpark();
+    }
+}
+
+/// Keeps track of the running subscriptions of an app.
+pub(crate) struct Tracker<Message> {
+    workers: HashMap<u64, Handle>,
+    resize: Vec<Box<dyn Fn(u16, u16) -> Message>>,
+}
+
+impl<Message: 'static> Tracker<Message> {
+    pub(crate) fn new() -> Self {
+        Self {
+            workers: HashMap::new(),
+            resize: Vec::new(),
+        }
+    }
+
+    /// Start the new subscriptions and stop the ones no longer returned.
+    pub(crate) fn update(
+        &mut self,
+        subscription: Subscription<Message>,
+        send: impl Fn(Message) + Clone + Send + 'static,
+    ) {
+        let mut workers = HashMap::with_capacity(subscription.recipes.len());
+        self.resize.clear();
+        for recipe in subscription.recipes {
+            match recipe.kind {
+                Kind::Resize(map) => self.resize.push(map),
+                Kind::Worker(worker) => {
+                    if workers.contains_key(&recipe.id) {
+                        continue;
+                    }
+                    let handle = self.workers.remove(&recipe.id).unwrap_or_else(|| {
+                        let running = Arc::new(AtomicBool::new(true));
+                        let flag = running.clone();
+                        let send = send.clone();
+                        let thread = thread::spawn(move || worker(flag, Box::new(send)));
+                        Handle {
+                            running,
+                            thread: thread.thread().clone(),
+                        }
+                    });
+                    workers.insert(recipe.id, handle);
+                }
+            }
+        }
+        // The handles left are dropped, which stops their workers.
+        self.workers = workers;
+    }
+
+    /// Map a terminal resize event into messages.
+    pub(crate) fn resize(&self, width: u16, height: u16) -> Vec<Message> {
+        self.resize.iter().map(|map| map(width, height)).collect()
+    }
+
+    /// Stop all the subscriptions.
+    pub(crate) fn clear(&mut self) {
+        self.workers.clear();
+        self.resize.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{mpsc, Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use super::{Subscription, Tracker};
+
+    #[test]
+    fn track() {
+        let (sender, receiver) = mpsc::channel();
+        let (external, external_receiver) = mpsc::channel();
+        let external_receiver = Arc::new(Mutex::new(external_receiver));
+        let subscription = || {
+            Subscription::batch([
+                Subscription::every("tick", Duration::from_millis(5), |_| 0),
+                Subscription::every("tock", Duration::from_millis(5), |_| 1),
+                Subscription::channel("external", external_receiver.clone(), |value| value),
+                Subscription::resize(|width, height| (width * height) as i32),
+            ])
+        };
+
+        let mut tracker = Tracker::new();
+        let send = move |message| sender.send(message).unwrap_or(());
+        tracker.update(subscription(), send.clone());
+        tracker.update(subscription(), send.clone());
+        assert_eq!(tracker.workers.len(), 3);
+        assert_eq!(tracker.resize(3, 4), vec![12]);
+
+        let deadline = Instant::now() + Duration::from_secs(1);
+        let mut received = Vec::new();
+        external.send(42).unwrap();
+        while !(received.contains(&0) && received.contains(&1) && received.contains(&42)) {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            received.push(receiver.recv_timeout(timeout).unwrap());
+        }
+
+        tracker.update(Subscription::none(), send);
+        assert!(tracker.workers.is_empty());
+        assert!(tracker.resize(3, 4).is_empty());
+    }
+}
@@ -1,6 +1,22 @@
-use unicode_width::UnicodeWidthChar;
+pub mod layout;
+pub mod markup;
 
-use crate::geometry::size::Size;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{geometry::size::Size, style::style::Style};
+
+use self::layout::TextLayout;
+
+/// Get the display width of a grapheme cluster. Graphemes with control characters are
+/// not displayed, and the others take one or two columns in a terminal.
+pub fn grapheme_width(grapheme: &str) -> u16 {
+    if grapheme.chars().any(char::is_control) {
+        0
+    } else {
+        (grapheme.width() as u16).min(2)
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Text {
@@ -28,18 +44,201 @@ impl Text {
         self.size = Self::size_of(&self.raw);
     }
 
+    /// Get the size of the content once rendered, see [`TextLayout::measure`].
     pub fn size_of(content: impl AsRef<str>) -> Size<u16> {
-        let mut size = Size::new(0, 0);
-
-        for line in content.as_ref().lines() {
-            let mut width = 0;
-            size.height += 1;
-            for char in line.chars() {
-                width += char.width().unwrap();
-            }
-            size.width = size.width.max(width as u16);
+        TextLayout::new().measure(&StyledText::from(content.as_ref()), None)
+    }
+}
+
+/// A piece of text on a single line with its own style.
+/// The style is patched on the style of the widget rendering it, see [`Style::patch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub content: String,
+    pub style: Style,
+}
+
+impl Span {
+    /// Create a span without style.
+    pub fn new(content: impl Into<String>) -> Self {
+        Self::styled(content, Style::new())
+    }
+
+    pub fn styled(content: impl Into<String>, style: Style) -> Self {
+        Self {
+            content: content.into(),
+            style,
+        }
+    }
+
+    /// Get the display width of the span.
+    pub fn width(&self) -> u16 {
+        self.content.graphemes(true).map(grapheme_width).sum()
+    }
+}
+
+/// A line of spans.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Line {
+    pub spans: Vec<Span>,
+}
+
+impl Line {
+    pub fn new(spans: impl IntoIterator<Item = Span>) -> Self {
+        Self {
+            spans: spans.into_iter().collect(),
         }
+    }
+
+    /// Add a span at the end of the line.
+    pub fn push(mut self, span: impl Into<Span>) -> Self {
+        self.spans.push(span.into());
+        self
+    }
+
+    /// Get the display width of the line.
+    pub fn width(&self) -> u16 {
+        self.spans.iter().map(Span::width).sum()
+    }
+
+    /// Get the content of the line without styles.
+    pub fn raw(&self) -> String {
+        self.spans
+            .iter()
+            .map(|span| span.content.as_str())
+            .collect()
+    }
+}
+
+/// Text made of lines of styled spans.
+///
+/// Plain strings convert into a single unstyled span per line, so widgets can accept
+/// `impl Into<StyledText>` for both.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyledText {
+    pub lines: Vec<Line>,
+}
+
+impl StyledText {
+    pub fn new(lines: impl IntoIterator<Item = Line>) -> Self {
+        Self {
+            lines: lines.into_iter().collect(),
+        }
+    }
+
+    /// Add a line at the end of the text.
+    pub fn push(mut self, line: impl Into<Line>) -> Self {
+        self.lines.push(line.into());
+        self
+    }
+
+    /// Get the size of the text once rendered without wrapping, i.e. the width of the
+    /// widest line and the number of lines, see [`TextLayout::measure`].
+    pub fn size(&self) -> Size {
+        TextLayout::new().measure(self, None)
+    }
+
+    /// Get the content of the text without styles, with the lines separated by `\n`.
+    pub fn raw(&self) -> String {
+        self.lines
+            .iter()
+            .map(Line::raw)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl From<&str> for Span {
+    fn from(content: &str) -> Self {
+        Self::new(content)
+    }
+}
+
+impl From<String> for Span {
+    fn from(content: String) -> Self {
+        Self::new(content)
+    }
+}
+
+impl From<Span> for Line {
+    fn from(span: Span) -> Self {
+        Self { spans: vec![span] }
+    }
+}
+
+impl From<Vec<Span>> for Line {
+    fn from(spans: Vec<Span>) -> Self {
+        Self { spans }
+    }
+}
+
+impl From<&str> for Line {
+    fn from(content: &str) -> Self {
+        Span::new(content).into()
+    }
+}
+
+impl From<String> for Line {
+    fn from(content: String) -> Self {
+        Span::new(content).into()
+    }
+}
+
+impl From<Line> for StyledText {
+    fn from(line: Line) -> Self {
+        Self { lines: vec![line] }
+    }
+}
+
+impl From<Vec<Line>> for StyledText {
+    fn from(lines: Vec<Line>) -> Self {
+        Self { lines }
+    }
+}
+
+impl From<Span> for StyledText {
+    fn from(span: Span) -> Self {
+        Line::from(span).into()
+    }
+}
+
+impl From<Vec<Span>> for StyledText {
+    fn from(spans: Vec<Span>) -> Self {
+        Line::from(spans).into()
+    }
+}
+
+impl From<&str> for StyledText {
+    fn from(content: &str) -> Self {
+        Self::new(content.lines().map(Line::from))
+    }
+}
+
+impl From<String> for StyledText {
+    fn from(content: String) -> Self {
+        content.as_str().into()
+    }
+}
+
+impl From<&String> for StyledText {
+    fn from(content: &String) -> Self {
+        content.as_str().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::size::Size, style::style::Style};
+
+    use super::{Line, Span, StyledText};
 
-        size
+    #[test]
+    fn styled_text() {
+        let text = StyledText::from("plain\n你好")
+            .push(Line::from("status: ").push(Span::styled("ok", Style::new().bold())));
+        assert_eq!(text.lines.len(), 3);
+        assert_eq!(text.size(), Size::new(10, 3));
+        assert_eq!(text.raw(), "plain\n你好\nstatus: ok");
+        assert_eq!(text.lines[2].spans[1].style, Style::new().bold());
     }
 }
@@ -0,0 +1,372 @@
+use std::mem;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{geometry::size::Size, style::style::Style};
+
+use super::{grapheme_width, Line, StyledText};
+
+/// The grapheme ending truncated rows.
+const ELLIPSIS: &str = "…";
+
+/// How the rows of a text are placed horizontally in their box.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Stretch the spaces of wrapped rows so that they fill the box. The last row of a
+    /// line is aligned on the left.
+    Justify,
+}
+
+/// A grapheme cluster placed by a [`TextLayout`], `x` being relative to the box of the
+/// text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyph<'a> {
+    pub x: u16,
+    pub grapheme: &'a str,
+    pub width: u16,
+    pub style: Style,
+}
+
+/// A grapheme cluster before being placed.
+#[derive(Debug, Clone, Copy)]
+struct Cell<'a> {
+    grapheme: &'a str,
+    width: u16,
+    style: Style,
+}
+
+impl Cell<'_> {
+    fn is_whitespace(&self) -> bool {
+        self.grapheme.chars().all(char::is_whitespace)
+    }
+}
+
+/// A row of cells, and whether it has been broken by wrapping.
+type Row<'a> = (Vec<Cell<'a>>, bool);
+
+/// Lays out styled text in a box: wraps lines on word boundaries, truncates what does
+/// not fit and aligns the rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextLayout {
+    wrap: bool,
+    alignment: Alignment,
+    truncate: bool,
+}
+
+impl Default for TextLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextLayout {
+    /// A layout wrapping lines and aligning them on the left, without ellipsis.
+    pub const fn new() -> Self {
+        Self {
+            wrap: true,
+            alignment: Alignment::Left,
+            truncate: false,
+        }
+    }
+
+    /// Wrap lines wider than the box on word boundaries. Words wider than the box are
+    /// broken anywhere. Otherwise, lines are cut.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// End the rows which are cut, and the last row if rows are cut, with `…`.
+    pub fn truncate(mut self, truncate: bool) -> Self {
+        self.truncate = truncate;
+        self
+    }
+
+    /// Lay out text in a box of `width` columns and `height` rows, unbounded if `None`.
+    /// The styles of the spans are patched on `style`.
+    pub fn rows<'a>(
+        &self,
+        text: &'a StyledText,
+        style: Style,
+        width: Option<u16>,
+        height: Option<u16>,
+    ) -> Vec<Vec<Glyph<'a>>> {
+        self.lines(text, style, width, height)
+            .into_iter()
+            .map(|(cells, wrapped)| self.place(cells, width, wrapped))
+            .collect()
+    }
+
+    /// Get the size of the text laid out in a box of `width` columns, unbounded if
+    /// `None`. This is the size the text takes once rendered.
+    pub fn measure(&self, text: &StyledText, width: Option<u16>) -> Size {
+        let rows = self.lines(text, Style::new(), width, None);
+        Size::new(
+            rows.iter()
+                .map(|(cells, _)| row_width(cells))
+                .max()
+                .unwrap_or(0),
+            rows.len() as u16,
+        )
+    }
+
+    /// Wrap and truncate the lines into rows.
+    fn lines<'a>(
+        &self,
+        text: &'a StyledText,
+        style: Style,
+        width: Option<u16>,
+        height: Option<u16>,
+    ) -> Vec<Row<'a>> {
+        let max_width = width.unwrap_or(u16::MAX);
+        let mut rows = Vec::new();
+        for line in &text.lines {
+            let cells = cells(line, style);
+            if self.wrap && width.is_some() {
+                rows.extend(wrap(cells, max_width));
+            } else {
+                rows.push((cells, false));
+            }
+        }
+
+        let cut = height.is_some_and(|height| rows.len() > height as usize);
+        if let Some(height) = height {
+            rows.truncate(height as usize);
+        }
+        let count = rows.len();
+        for (i, (cells, _)) in rows.iter_mut().enumerate() {
+            let overflow = row_width(cells) > max_width;
+            if overflow || (cut && self.truncate && i + 1 == count) {
+                self.fit(cells, max_width, style);
+            }
+        }
+        rows
+    }
+
+    /// Cut a row to `width` columns, ending it with `…` if truncating.
+    fn fit(&self, cells: &mut Vec<Cell>, width: u16, style: Style) {
+        let room = if self.truncate {
+            width.saturating_sub(1)
+        } else {
+            width
+        };
+        while row_width(cells) > room {
+            cells.pop();
+        }
+        if self.truncate && width > 0 {
+            let style = cells.last().map_or(style, |cell| cell.style);
+            cells.push(Cell {
+                grapheme: ELLIPSIS,
+                width: 1,
+                style,
+            });
+        }
+    }
+
+    /// Place the cells of a row according to the alignment.
+    fn place<'a>(&self, cells: Vec<Cell<'a>>, width: Option<u16>, wrapped: bool) -> Vec<Glyph<'a>> {
+        let free = width.map_or(0, |width| width.saturating_sub(row_width(&cells)));
+        let mut x = match self.alignment {
+            Alignment::Left | Alignment::Justify => 0,
+            Alignment::Center => free / 2,
+            Alignment::Right => free,
+        };
+        // With justify, spread the free columns over the spaces of the row.
+        let spaces = cells.iter().filter(|cell| cell.grapheme == " ").count() as u16;
+        let justify = self.alignment == Alignment::Justify && wrapped && spaces > 0;
+        let mut space = 0;
+        cells
+            .into_iter()
+            .map(|cell| {
+                let glyph = Glyph {
+                    x,
+                    grapheme: cell.grapheme,
+                    width: cell.width,
+                    style: cell.style,
+                };
+                x += cell.width;
+                if justify && cell.grapheme == " " {
+                    x += free / spaces + (space < free % spaces) as u16;
+                    space += 1;
+                }
+                glyph
+            })
+            .collect()
+    }
+}
+
+/// Get the cells of a line, without the graphemes which are not displayed.
+fn cells(line: &Line, style: Style) -> Vec<Cell<'_>> {
+    line.spans
+        .iter()
+        .flat_map(|span| {
+            let style = style.patch(span.style);
+            span.content.graphemes(true).map(move |grapheme| Cell {
+                grapheme,
+                width: grapheme_width(grapheme),
+                style,
+            })
+        })
+        .filter(|cell| cell.width > 0)
+        .collect()
+}
+
+fn row_width(cells: &[Cell]) -> u16 {
+    cells.iter().map(|cell| cell.width).sum()
+}
+
+/// Break a line into rows of at most `width` columns, on word boundaries. The spaces
+/// where a row is broken are dropped.
+fn wrap(cells: Vec<Cell<'_>>, width: u16) -> Vec<Row<'_>> {
+    let raw: String = cells.iter().map(|cell| cell.grapheme).collect();
+    let mut cells = cells.into_iter();
+    // The byte offset in `raw` of the next cell.
+    let mut offset = 0;
+    let mut rows = Vec::new();
+    let mut row: Vec<Cell> = Vec::new();
+    let mut row_width = 0;
+
+    for (start, word) in raw.split_word_bound_indices() {
+        // A grapheme may span several words when the spans split it, keep it whole.
+        let mut cells_of_word = Vec::new();
+        while offset < start + word.len() {
+            let Some(cell) = cells.next() else { break };
+            offset += cell.grapheme.len();
+            cells_of_word.push(cell);
+        }
+        let word = cells_of_word;
+        if word.is_empty() {
+            continue;
+        }
+        let word_width = self::row_width(&word);
+        let blank = word.iter().all(Cell::is_whitespace);
+        if blank && row.is_empty() && !rows.is_empty() {
+            continue;
+        }
+        if row_width + word_width <= width {
+            row_width += word_width;
+            row.extend(word);
+            continue;
+        }
+        if blank {
+            if !row.is_empty() {
+                rows.push((mem::take(&mut row), true));
+                row_width = 0;
+            }
+            continue;
+        }
+        if !row.is_empty() {
+            while row.last().is_some_and(Cell::is_whitespace) {
+                row.pop();
+            }
+            rows.push((mem::take(&mut row), true));
+            row_width = 0;
+        }
+        if word_width <= width {
+            row_width = word_width;
+            row = word;
+            continue;
+        }
+        // Break a word wider than the box anywhere.
+        for cell in word.into_iter().filter(|cell| cell.width <= width) {
+            if row_width + cell.width > width {
+                rows.push((mem::take(&mut row), true));
+                row_width = 0;
+            }
+            row_width += cell.width;
+            row.push(cell);
+        }
+    }
+    rows.push((row, false));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::size::Size, style::style::Style, text::StyledText};
+
+    use super::{Alignment, TextLayout};
+
+    fn lay_out(layout: TextLayout, text: &str, width: u16, height: Option<u16>) -> Vec<String> {
+        layout
+            .rows(&StyledText::from(text), Style::new(), Some(width), height)
+            .into_iter()
+            .map(|row| {
+                let mut line = String::new();
+                let mut x = 0;
+                for glyph in row {
+                    line.push_str(&".".repeat((glyph.x - x) as usize));
+                    line.push_str(glyph.grapheme);
+                    x = glyph.x + glyph.width;
+                }
+                line
+            })
+            .collect()
+    }
+
+    #[test]
+    fn wrap() {
+        let layout = TextLayout::new();
+        assert_eq!(
+            lay_out(layout, "the quick brown fox", 10, None),
+            vec!["the quick", "brown fox"]
+        );
+        assert_eq!(
+            lay_out(layout, "a supercalifragilistic b", 8, None),
+            vec!["a", "supercal", "ifragili", "stic b"]
+        );
+        assert_eq!(
+            lay_out(layout, "你好，世界", 6, None),
+            vec!["你好，", "世界"]
+        );
+        assert_eq!(
+            layout.measure(&StyledText::from("the quick brown fox"), Some(10)),
+            Size::new(9, 2)
+        );
+        assert_eq!(
+            layout.measure(&StyledText::from("ab\n你好 c"), None),
+            Size::new(6, 2)
+        );
+    }
+
+    #[test]
+    fn align() {
+        let layout = |alignment| TextLayout::new().alignment(alignment);
+        let text = "the quick brown fox jumps";
+        assert_eq!(
+            lay_out(layout(Alignment::Center), text, 12, None),
+            vec![".the quick", ".brown fox", "...jumps"]
+        );
+        assert_eq!(
+            lay_out(layout(Alignment::Right), text, 12, None),
+            vec!["...the quick", "...brown fox", ".......jumps"]
+        );
+        assert_eq!(
+            lay_out(layout(Alignment::Justify), text, 12, None),
+            vec!["the ...quick", "brown ...fox", "jumps"]
+        );
+    }
+
+    #[test]
+    fn truncate() {
+        let layout = TextLayout::new().truncate(true);
+        assert_eq!(
+            lay_out(layout.wrap(false), "hello world", 8, None),
+            vec!["hello w…"]
+        );
+        assert_eq!(
+            lay_out(layout, "one two three four", 9, Some(2)),
+            vec!["one two", "three…"]
+        );
+        assert_eq!(lay_out(layout, "short", 9, Some(2)), vec!["short"]);
+    }
+}
@@ -0,0 +1,245 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::style::{attributes::Attributes, color::Color, rgb::RGB, style::Style};
+
+use super::{Line, Span, StyledText};
+
+/// What is wrong in markup, see [`MarkupError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkupErrorKind {
+    /// A `[` without a matching `]`.
+    UnclosedTag,
+    /// A tag without any word, i.e. `[]`.
+    EmptyTag,
+    /// A word which is neither a color nor an attribute.
+    UnknownStyle(String),
+    /// An `on` not followed by a color.
+    ExpectedColor,
+    /// A closing tag while no tag is open.
+    UnexpectedClose,
+    /// A closing tag repeating another tag than the open one.
+    MismatchedClose { expected: String, found: String },
+}
+
+/// An error in markup, see [`parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupError {
+    pub kind: MarkupErrorKind,
+    /// The byte offset of the faulty tag in the markup.
+    pub position: usize,
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            MarkupErrorKind::UnclosedTag => write!(f, "unclosed tag, expected `]`")?,
+            MarkupErrorKind::EmptyTag => write!(f, "empty tag `[]`")?,
+            MarkupErrorKind::UnknownStyle(word) => write!(
+                f,
+                "unknown style `{}`, expected a color or an attribute",
+                word
+            )?,
+            MarkupErrorKind::ExpectedColor => write!(f, "expected a color after `on`")?,
+            MarkupErrorKind::UnexpectedClose => write!(f, "closing tag without an open tag")?,
+            MarkupErrorKind::MismatchedClose { expected, found } => write!(
+                f,
+                "closing tag `[/{}]` does not match the open tag `[{}]`",
+                found, expected
+            )?,
+        }
+        write!(f, " at byte {}", self.position)
+    }
+}
+
+impl Error for MarkupError {}
+
+/// Parse a small markup language into styled text.
+///
+/// A tag like `[bold primary]` opens a style, patched on the style of the enclosing
+/// tags, and `[/]` closes the last open tag, e.g. `[bold primary]Saved[/] 3 files`.
+/// A tag is a list of words separated by spaces:
+///
+/// - an attribute name, e.g. `bold` or `underline`;
+/// - a color, either a theme color name like `primary` or a hex code like `#7AA2F7`,
+///   for the foreground;
+/// - `on` followed by a color, for the background.
+///
+/// A closing tag may repeat the open tag, e.g. `[/bold primary]`, to be checked. The
+/// spaces between the words do not matter. Tags still open at the end are closed
+/// implicitly. `\[` is a literal `[` and `\\` a literal `\`.
+pub fn parse(markup: &str) -> Result<StyledText, MarkupError> {
+    let error = |kind, position| MarkupError { kind, position };
+    // The open tags, with the styles they result in.
+    let mut open: Vec<(String, Style)> = Vec::new();
+    let mut style = Style::new();
+    let mut lines = vec![Line::default()];
+    let mut content = String::new();
+
+    let flush = |lines: &mut Vec<Line>, content: &mut String, style: Style| {
+        if !content.is_empty() {
+            let line = lines.last_mut().unwrap();
+            line.spans
+                .push(Span::styled(std::mem::take(content), style));
+        }
+    };
+
+    let mut chars = markup.char_indices().peekable();
+    while let Some((position, char)) = chars.next() {
+        match char {
+            '\\' => match chars.peek() {
+                Some((_, escaped @ ('[' | '\\'))) => {
+                    content.push(*escaped);
+                    chars.next();
+                }
+                _ => content.push('\\'),
+            },
+            '\n' => {
+                flush(&mut lines, &mut content, style);
+                lines.push(Line::default());
+            }
+            '[' => {
+                let length = markup[position..]
+                    .find(']')
+                    .ok_or(error(MarkupErrorKind::UnclosedTag, position))?;
+                let tag = markup[position + 1..position + length].trim();
+                while chars.next_if(|(i, _)| *i <= position + length).is_some() {}
+
+                flush(&mut lines, &mut content, style);
+                if let Some(closed) = tag.strip_prefix('/') {
+                    let closed = normalize(closed);
+                    let (tag, _) = open
+                        .pop()
+                        .ok_or(error(MarkupErrorKind::UnexpectedClose, position))?;
+                    if !closed.is_empty() && closed != tag {
+                        let kind = MarkupErrorKind::MismatchedClose {
+                            expected: tag,
+                            found: closed,
+                        };
+                        return Err(error(kind, position));
+                    }
+                    style = open.last().map(|(_, style)| *style).unwrap_or_default();
+                } else {
+                    style = style.patch(parse_tag(tag).map_err(|kind| error(kind, position))?);
+                    open.push((normalize(tag), style));
+                }
+            }
+            char => content.push(char),
+        }
+    }
+    flush(&mut lines, &mut content, style);
+    Ok(StyledText::new(lines))
+}
+
+/// Get the words of a tag separated by single spaces, to compare closing tags.
+fn normalize(tag: &str) -> String {
+    tag.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse the words of an opening tag into a style.
+fn parse_tag(tag: &str) -> Result<Style, MarkupErrorKind> {
+    let mut words = tag.split_whitespace();
+    let mut style = Style::new();
+    let mut empty = true;
+    while let Some(word) = words.next() {
+        empty = false;
+        if word == "on" {
+            let color = words.next().and_then(parse_color);
+            style.background = Some(color.ok_or(MarkupErrorKind::ExpectedColor)?);
+        } else if let Some(attributes) = Attributes::from_name(word) {
+            style.attributes = style.attributes.union(attributes);
+        } else if let Some(color) = parse_color(word) {
+            style.foreground = Some(color);
+        } else {
+            return Err(MarkupErrorKind::UnknownStyle(word.to_string()));
+        }
+    }
+    if empty {
+        return Err(MarkupErrorKind::EmptyTag);
+    }
+    Ok(style)
+}
+
+/// Parse a theme color name or a hex code like `#7AA2F7`.
+fn parse_color(word: &str) -> Option<Color> {
+    match word.strip_prefix('#') {
+        Some(hex) if hex.len() == 6 => u32::from_str_radix(hex, 16)
+            .ok()
+            .map(|hex| Color::RGB(RGB::from_hex(hex))),
+        Some(_) => None,
+        None => Color::from_name(word),
+    }
+}
+
+/// Parse markup into styled text, see [`parse`].
+impl FromStr for StyledText {
+    type Err = MarkupError;
+
+    fn from_str(markup: &str) -> Result<Self, Self::Err> {
+        parse(markup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        style::{color::Color, rgb::RGB, style::Style},
+        text::{Line, Span, StyledText},
+    };
+
+    use super::{parse, MarkupError, MarkupErrorKind};
+
+    #[test]
+    fn styles() {
+        let text =
+            parse("[bold primary]Saved[/] 3 files\n[italic]a [#FF0000 on danger]b\\[c[/]").unwrap();
+        let bold = Style::new().foreground(Color::Primary).bold();
+        let italic = Style::new().italic();
+        assert_eq!(
+            text,
+            StyledText::new([
+                Line::new([Span::styled("Saved", bold), Span::new(" 3 files")]),
+                Line::new([
+                    Span::styled("a ", italic),
+                    Span::styled(
+                        "b[c",
+                        italic
+                            .foreground(RGB::from_hex(0xFF0000))
+                            .background(Color::Danger)
+                    ),
+                ]),
+            ])
+        );
+        assert_eq!("[/bold]".parse::<StyledText>().unwrap_err().position, 0);
+        assert_eq!(
+            parse("[bold  primary]Saved[/ bold primary ]").unwrap(),
+            StyledText::new([Line::new([Span::styled("Saved", bold)])])
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |markup| parse(markup).unwrap_err();
+        assert_eq!(
+            error("ok [blod]x"),
+            MarkupError {
+                kind: MarkupErrorKind::UnknownStyle("blod".to_string()),
+                position: 3,
+            }
+        );
+        assert_eq!(
+            error("ok [blod]x").to_string(),
+            "unknown style `blod`, expected a color or an attribute at byte 3"
+        );
+        assert_eq!(error("[bold").kind, MarkupErrorKind::UnclosedTag);
+        assert_eq!(error("[ ]").kind, MarkupErrorKind::EmptyTag);
+        assert_eq!(error("[on]").kind, MarkupErrorKind::ExpectedColor);
+        assert_eq!(error("x[/]").kind, MarkupErrorKind::UnexpectedClose);
+        assert_eq!(
+            error("[bold]x[/italic]").kind,
+            MarkupErrorKind::MismatchedClose {
+                expected: "bold".to_string(),
+                found: "italic".to_string(),
+            }
+        );
+    }
+}
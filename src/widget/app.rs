@@ -1,55 +1,91 @@
 use std::{
-    io::{stdout, Result, Write},
-    marker::PhantomData,
-    sync::{mpsc, Arc, Mutex},
-    thread::{self, JoinHandle},
+    any::Any,
+    collections::HashMap,
+    io::Result,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
     time::{Duration, Instant},
-    u16,
 };
 
-use crossterm::{
-    cursor, event::{DisableMouseCapture, EnableMouseCapture, Event, KeyCode}, terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen}, ExecutableCommand, QueueableCommand
-};
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseEvent};
 
 use crate::{
+    backend::{backend::Backend, crossterm_backend::CrosstermBackend},
     buffer::buffer::Buffer,
+    command::Command,
     geometry::{area::Area, length::Length, size::Size},
     shell::Shell,
     style::{
         color::{Color, ColorSystem},
         theme::Theme,
     },
-    terminal,
+    subscription::{Subscription, Tracker},
 };
 
 use super::{element::Element, widget::Widget};
 
+/// The opacity of the colors of the view below a modal.
+const BACKDROP_ALPHA: u8 = 0x60;
+
 pub trait Program<'a, Message: Clone> {
+    /// Returns the command to run when the app starts.
+    fn init(&mut self) -> Command<Message> {
+        Command::none()
+    }
+
     /// Update the program state with a message.
-    fn update(&mut self, message: Message);
+    /// The returned command is run off the main loop, and the messages it produces
+    /// are given back to this function.
+    fn update(&mut self, message: Message) -> Command<Message>;
 
     /// Returns the element to be rendered.
     fn view(&self) -> Element<'a, Message>;
 
+    /// Returns the modal element to be rendered centered above the view, if any.
+    /// While a modal is shown, the view is dimmed and the modal takes the focus and all
+    /// the events, until the program dismisses it by returning `None` after a message.
+    fn modal(&self) -> Option<Element<'a, Message>> {
+        None
+    }
+
+    /// Returns the subscriptions of the program, asked on every frame.
+    fn subscription(&self) -> Subscription<Message> {
+        Subscription::none()
+    }
+
     /// Returns the theme of the program.
     fn theme(&self) -> Theme {
         Theme::TOKYO_NIGHT
     }
 }
 
-pub struct App<Message, Program>
+pub struct App<Message, Program, Backend = CrosstermBackend>
 where
-    Message: Clone,
+    Message: Clone + Send + 'static,
     Program: for<'a> self::Program<'a, Message>,
+    Backend: self::Backend,
 {
-    running: Arc<Mutex<bool>>,
+    running: bool,
+    /// Whether [`Program::init`] has been called.
+    initialized: bool,
     framerate: FrameRate,
     color_system: ColorSystem,
     background: Option<Color>,
-    event_thread: Option<JoinHandle<()>>,
     quit_key: KeyCode,
+    /// The last frame drawn by the backend, used to only output the changed pixels.
+    last_frame: Option<Buffer>,
+    /// The index of the focused widget in the focus chain, see [`Self::apply_focus`].
+    focus: Option<usize>,
+    /// The focus of the view while a modal is shown, given back when it is dismissed.
+    view_focus: Option<Option<usize>>,
+    /// The internal states of the widgets, indexed by their paths in the element tree.
+    states: HashMap<Vec<usize>, Box<dyn Any>>,
+    /// The channel of the messages produced by commands and subscriptions.
+    sender: Sender<Message>,
+    receiver: Receiver<Message>,
+    subscriptions: Tracker<Message>,
+    backend: Backend,
     program: Program,
-    _message: PhantomData<Message>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -60,19 +96,42 @@ pub enum FrameRate {
 
 impl<Message, Program> App<Message, Program>
 where
-    Message: Clone,
+    Message: Clone + Send + 'static,
     Program: for<'a> self::Program<'a, Message>,
 {
     pub fn new(instance: Program) -> Self {
+        Self::with_backend(instance, CrosstermBackend::new())
+    }
+}
+
+impl<Message, Program, Backend> App<Message, Program, Backend>
+where
+    Message: Clone + Send + 'static,
+    Program: for<'a> self::Program<'a, Message>,
+    Backend: self::Backend,
+{
+    /// Create an app drawing to the given backend, e.g. a [`TestBackend`] to run the
+    /// app headless.
+    ///
+    /// [`TestBackend`]: crate::backend::test_backend::TestBackend
+    pub fn with_backend(instance: Program, backend: Backend) -> Self {
+        let (sender, receiver) = mpsc::channel();
         Self {
-            running: Arc::new(Mutex::new(false)),
+            running: false,
+            initialized: false,
             framerate: FrameRate::Max(60),
-            color_system: terminal::detect_color_system(),
+            color_system: backend.color_system(),
             background: None,
-            event_thread: None,
             quit_key: KeyCode::Char('q'),
+            last_frame: None,
+            focus: None,
+            view_focus: None,
+            states: HashMap::new(),
+            sender,
+            receiver,
+            subscriptions: Tracker::new(),
+            backend,
             program: instance,
-            _message: PhantomData,
         }
     }
 
@@ -96,46 +155,34 @@ where
         self
     }
 
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn backend(&self) -> &Backend {
+        &self.backend
+    }
+
+    pub fn backend_mut(&mut self) -> &mut Backend {
+        &mut self.backend
+    }
+
     /// Run the app and enter the main loop.
     /// This function will change the terminal environment until [`Self::stop`] is called.
     pub fn run(&mut self) -> Result<()> {
         // Check if the app has been already run.
-        if *self.running.lock().unwrap() || self.event_thread.is_some() {
+        if self.running {
             return Ok(());
         }
 
-        Self::init_fullscreen().unwrap();
-        *self.running.lock().unwrap() = true;
-
-        // Start event thread.
-        let event_thread_running = self.running.clone();
-        let (sender, receiver) = mpsc::channel();
-        self.event_thread = Some(thread::spawn(move || {
-            while *event_thread_running.lock().unwrap() {
-                let event = crossterm::event::read().unwrap();
-                sender.send(event).unwrap();
-            }
-        }));
+        self.backend.init()?;
+        self.running = true;
 
         let mut timepoint = Instant::now();
-        // Main output and event processing loop.
-        while *self.running.lock().unwrap() {
-            // First, draw the widget.
-            let mut element = self.program.view();
-            let widget = element.widget_mut();
-            self.draw(widget, &self.program.theme())?;
-
-            // Second, process events.
-            while let Ok(event) = receiver.try_recv() {
-                let mut shell = Shell::new();
-                widget.process_event(event.clone(), &mut shell);
-                self.process_event(event, &mut shell);
-                for message in shell.messages() {
-                    self.program.update(message.clone());
-                }
-            }
+        while self.running {
+            self.step()?;
 
-            // Third, sleep to limit the frame rate.
+            // Sleep to limit the frame rate.
             if let FrameRate::Max(fps) = self.framerate {
                 let elapsed = timepoint.elapsed();
                 let target = Duration::from_millis(1000 / fps as u64);
@@ -146,83 +193,377 @@ where
             }
         }
 
-        self.stop()?;
+        self.stop()
+    }
+
+    /// Run a single iteration of the main loop: draw a frame, then process the pending
+    /// events of the backend and the messages produced by commands and subscriptions.
+    /// The effects of the events are visible in the next frame.
+    ///
+    /// Called repeatedly by [`Self::run`], or directly to drive the app headless.
+    pub fn step(&mut self) -> Result<()> {
+        if !self.initialized {
+            self.initialized = true;
+            let command = self.program.init();
+            self.execute(command);
+        }
+
+        // First, start and stop the subscriptions.
+        let sender = self.sender.clone();
+        self.subscriptions
+            .update(self.program.subscription(), move |message| {
+                let _ = sender.send(message);
+            });
+
+        // Second, draw the widgets. A modal is the last root, and traps the focus and
+        // the events.
+        let mut element = self.program.view();
+        let mut modal = self.program.modal();
+        match (&modal, self.view_focus) {
+            (Some(_), None) => {
+                self.view_focus = Some(self.focus.take());
+                self.focus = Some(0);
+            }
+            (None, Some(focus)) => {
+                self.view_focus = None;
+                self.focus = focus;
+            }
+            _ => {}
+        }
+        let mut roots = vec![element.widget_mut()];
+        if let Some(modal) = modal.as_mut() {
+            Self::apply_focus(roots[0], None);
+            roots.push(modal.widget_mut());
+        }
+        self.restore_states(&mut roots);
+        let widget = &mut **roots.last_mut().unwrap();
+        // Keep the focus inside the focus chain, which may be shortened by an update.
+        let count = Self::apply_focus(widget, self.focus);
+        if self.focus.is_some_and(|focus| focus >= count) {
+            self.focus = count.checked_sub(1);
+            Self::apply_focus(widget, self.focus);
+        }
+        let theme = self.program.theme();
+        self.draw(&mut roots, &theme)?;
+        let widget = &mut **roots.last_mut().unwrap();
+
+        // Third, process the messages produced by commands and subscriptions, then the
+        // events.
+        while let Ok(message) = self.receiver.try_recv() {
+            self.update(message);
+        }
+        while let Some(event) = self.backend.poll_event()? {
+            if let Event::Resize(width, height) = event {
+                for message in self.subscriptions.resize(width, height) {
+                    self.update(message);
+                }
+            }
+            let mut shell = Shell::new();
+            if let Event::Mouse(mouse_event) = event {
+                let viewport = Area::from_size(self.backend.size()?);
+                Self::process_overlay_event(widget, viewport, mouse_event, &mut shell);
+            }
+            if !shell.is_event_captured() {
+                widget.process_event(event.clone(), &mut shell);
+            }
+            self.process_event(widget, event, &mut shell)?;
+            for message in shell.messages() {
+                self.update(message.clone());
+            }
+        }
+        self.save_states(&mut roots);
         Ok(())
     }
 
     /// Stop the app and exit the main loop.
     /// This function will restore the terminal environment to the original state.
     pub fn stop(&mut self) -> Result<()> {
-        *self.running.lock().unwrap() = false;
+        if !self.running {
+            return Ok(());
+        }
+        self.running = false;
+        self.last_frame = None;
+        self.states.clear();
+        self.subscriptions.clear();
+        self.backend.restore()
+    }
+
+    /// Draw the view, and the modal if any, i.e. the roots after the first one.
+    fn draw(&mut self, roots: &mut [&mut dyn Widget<Message>], theme: &Theme) -> Result<()> {
+        let terminal_size = self.backend.size()?;
+        let terminal_area = Area::from_size(terminal_size);
+        let mut background = Buffer::new(terminal_area.width, terminal_area.height);
+        background.set_color_system(self.color_system);
+        background.render_background(terminal_area, self.background);
+        let Some((widget, modals)) = roots.split_first_mut() else {
+            return Ok(());
+        };
+        let area = Area::from_size(Self::root_size(*widget, terminal_size));
+        widget.layout(area);
+        widget.render(area, &mut background, theme);
+        Self::render_overlays(*widget, terminal_area, &mut background, theme);
 
-        // Stop event thread.
-        if let Some(thread) = self.event_thread.take() {
-            thread.join().unwrap();
+        for modal in modals {
+            let backdrop = theme.background.darker(0.5);
+            background.dim(terminal_area, BACKDROP_ALPHA, backdrop, theme);
+            let size = Self::root_size(*modal, terminal_size);
+            let size = Size::new(
+                size.width.min(terminal_size.width),
+                size.height.min(terminal_size.height),
+            );
+            let area = Area::new(
+                (terminal_size.width - size.width) / 2,
+                (terminal_size.height - size.height) / 2,
+                size.width,
+                size.height,
+            );
+            modal.layout(area);
+            background.render_background(area, Some(Color::Background));
+            modal.render(area, &mut background, theme);
+            Self::render_overlays(*modal, terminal_area, &mut background, theme);
         }
 
-        Self::uninit_fullscreen()?;
+        // Only output the difference to the last frame, unless the terminal has been
+        // resized.
+        let previous = self.last_frame.take().filter(|frame| {
+            frame.width() == background.width() && frame.height() == background.height()
+        });
+        self.backend
+            .draw(&background, previous.as_ref(), self.color_system, theme)?;
+        self.last_frame = Some(background);
         Ok(())
     }
 
-    fn init_fullscreen() -> Result<()> {
-        enable_raw_mode()?;
-        stdout().queue(cursor::Hide)?;
-        stdout().queue(EnableMouseCapture)?;
-        stdout().queue(EnterAlternateScreen)?;
-        stdout().flush()?;
-        Ok(())
+    /// Render the overlays shown by the widgets of a tree above it.
+    fn render_overlays(
+        widget: &mut dyn Widget<Message>,
+        viewport: Area,
+        buffer: &mut Buffer,
+        theme: &Theme,
+    ) {
+        Self::walk_origins(widget, (0, 0), &mut |(x, y), widget| {
+            if let Some(overlay) = widget.overlay(viewport.translate(-x, -y)) {
+                let area = overlay.translate(x, y).intersect(viewport);
+                widget.render_overlay(area, buffer, theme);
+            }
+        });
     }
 
-    fn uninit_fullscreen() -> Result<()> {
-        stdout().queue(LeaveAlternateScreen)?;
-        stdout().queue(DisableMouseCapture)?;
-        stdout().queue(cursor::Show)?;
-        stdout().flush()?;
-        disable_raw_mode()?;
-        Ok(())
+    /// Give a mouse event hitting an overlay to its widget before the tree, in the
+    /// coordinates of the widget, and capture it so that the tree below does not get it.
+    /// The other events only go through the tree.
+    fn process_overlay_event(
+        widget: &mut dyn Widget<Message>,
+        viewport: Area,
+        event: MouseEvent,
+        shell: &mut Shell<Message>,
+    ) {
+        Self::walk_origins(widget, (0, 0), &mut |(x, y), widget| {
+            if shell.is_event_captured() {
+                return;
+            }
+            let Some(overlay) = widget.overlay(viewport.translate(-x, -y)) else {
+                return;
+            };
+            let column = u16::try_from(event.column as i32 - x);
+            let row = u16::try_from(event.row as i32 - y);
+            if let (Ok(column), Ok(row)) = (column, row) {
+                if overlay.contains(column, row) {
+                    let event = MouseEvent {
+                        column,
+                        row,
+                        ..event
+                    };
+                    widget.process_event(Event::Mouse(event), shell);
+                    shell.capture_event();
+                }
+            }
+        });
     }
 
-    fn draw(&self, widget: &mut dyn Widget<Message>, theme: &Theme) -> Result<()> {
+    /// Get the size of a root widget from its size hint.
+    fn root_size(widget: &dyn Widget<Message>, terminal_size: Size) -> Size {
         let width = match widget.size_hint().width {
             Length::Preferred => widget.size().width,
             Length::Fixed(width) => width,
-            _ => terminal::size().width,
+            _ => terminal_size.width,
         };
         let height = match widget.size_hint().height {
-            Length::Preferred => widget.size().height,
+            Length::Preferred => widget.height_for_width(width),
             Length::Fixed(height) => height,
-            _ => terminal::size().height,
+            _ => terminal_size.height,
         };
-        let area = Area::from_size(Size::new(width, height));
-        let terminal_area = Area::from_size(terminal::size());
-        widget.layout(area);
-        let mut background = Buffer::new(terminal_area.width, terminal_area.height);
-        background.render_background(terminal_area, self.background);
-        widget.render(area, &mut background, theme);
-        stdout().execute(crossterm::cursor::MoveTo(0, 0))?;
-        print!("{}", background.view(self.color_system, theme));
-        Ok(())
+        Size::new(width, height)
     }
 
-    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+    /// Update the program with a message and run the returned command.
+    fn update(&mut self, message: Message) {
+        let command = self.program.update(message);
+        self.execute(command);
+    }
+
+    fn execute(&mut self, command: Command<Message>) {
+        if command.is_none() {
+            return;
+        }
+        let sender = self.sender.clone();
+        command.execute(move |message| {
+            // The app may have been dropped before the command finishes.
+            let _ = sender.send(message);
+        });
+    }
+
+    fn process_event(
+        &mut self,
+        widget: &mut dyn Widget<Message>,
+        event: Event,
+        shell: &mut Shell<Message>,
+    ) -> Result<()> {
+        if shell.is_focus_requested() {
+            self.take_requested_focus(widget);
+        }
         if shell.is_event_captured() {
+            return Ok(());
+        }
+        if let Event::Key(key_event) = event {
+            if key_event.kind == KeyEventKind::Press {
+                match key_event.code {
+                    code if code == self.quit_key => self.stop()?,
+                    KeyCode::Tab => self.move_focus(widget, true),
+                    KeyCode::BackTab => self.move_focus(widget, false),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Move the focus to the next (or previous) focusable widget, wrapping around.
+    fn move_focus(&mut self, widget: &mut dyn Widget<Message>, forward: bool) {
+        let count = Self::apply_focus(widget, None);
+        if count == 0 {
+            self.focus = None;
             return;
         }
-        match event {
-            Event::Key(key_event) => {
-                if key_event.code == self.quit_key {
-                    self.stop().unwrap();
+        self.focus = Some(match (self.focus, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+        });
+        Self::apply_focus(widget, self.focus);
+    }
+
+    /// Move the focus to the widget which has focused itself and requested the focus
+    /// through [`Shell::request_focus`].
+    fn take_requested_focus(&mut self, widget: &mut dyn Widget<Message>) {
+        let mut requested = None;
+        let mut index = 0;
+        Self::walk(widget, &mut Vec::new(), &mut |_, widget| {
+            if widget.is_focusable() {
+                if widget.is_focused() && self.focus != Some(index) && requested.is_none() {
+                    requested = Some(index);
                 }
+                index += 1;
             }
-            _ => {}
+        });
+        if requested.is_some() {
+            self.focus = requested;
+            Self::apply_focus(widget, self.focus);
+        }
+    }
+
+    /// Give the focus to the `focus`-th focusable widget of the focus chain and take
+    /// it from the others. The focus chain is all the focusable widgets of the tree
+    /// in depth-first order. Returns the length of the focus chain.
+    fn apply_focus(widget: &mut dyn Widget<Message>, focus: Option<usize>) -> usize {
+        let mut index = 0;
+        Self::walk(widget, &mut Vec::new(), &mut |_, widget| {
+            if widget.is_focusable() {
+                widget.set_focused(focus == Some(index));
+                index += 1;
+            }
+        });
+        index
+    }
+
+    /// Save the internal states of all the widgets in the trees. The paths start with
+    /// the index of the root, so that the view and the modal do not share states.
+    fn save_states(&mut self, roots: &mut [&mut dyn Widget<Message>]) {
+        let states = &mut self.states;
+        states.clear();
+        for (i, widget) in roots.iter_mut().enumerate() {
+            Self::walk_all(*widget, &mut vec![i], &mut |path, widget| {
+                if let Some(state) = widget.take_state() {
+                    states.insert(path.to_vec(), state);
+                }
+            });
+        }
+    }
+
+    /// Give the saved internal states back to the widgets at the same paths.
+    fn restore_states(&mut self, roots: &mut [&mut dyn Widget<Message>]) {
+        let states = &mut self.states;
+        for (i, widget) in roots.iter_mut().enumerate() {
+            Self::walk_all(*widget, &mut vec![i], &mut |path, widget| {
+                if let Some(state) = states.remove(path) {
+                    widget.restore(state);
+                }
+            });
+        }
+    }
+
+    /// Visit all the widgets of the tree in depth-first order, with their paths, i.e. the
+    /// indices of the children from the root to the widget.
+    fn walk(
+        widget: &mut dyn Widget<Message>,
+        path: &mut Vec<usize>,
+        f: &mut impl FnMut(&[usize], &mut dyn Widget<Message>),
+    ) {
+        f(path, widget);
+        for (i, child) in widget.children_mut().into_iter().enumerate() {
+            path.push(i);
+            Self::walk(child, path, f);
+            path.pop();
+        }
+    }
+
+    /// Like [`Self::walk`], but also visit the hidden children, see
+    /// [`Widget::all_children_mut`].
+    fn walk_all(
+        widget: &mut dyn Widget<Message>,
+        path: &mut Vec<usize>,
+        f: &mut impl FnMut(&[usize], &mut dyn Widget<Message>),
+    ) {
+        f(path, widget);
+        for (i, child) in widget.all_children_mut().into_iter().enumerate() {
+            path.push(i);
+            Self::walk_all(child, path, f);
+            path.pop();
+        }
+    }
+
+    /// Visit all the widgets of the tree in depth-first order, with the positions on the
+    /// screen of the origins of their coordinates.
+    fn walk_origins(
+        widget: &mut dyn Widget<Message>,
+        origin: (i32, i32),
+        f: &mut impl FnMut((i32, i32), &mut dyn Widget<Message>),
+    ) {
+        f(origin, widget);
+        let (x, y) = widget.children_origin();
+        let origin = (origin.0 + x, origin.1 + y);
+        for child in widget.children_mut() {
+            Self::walk_origins(child, origin, f);
         }
     }
 }
 
-impl<Message, Program> Widget<Message> for App<Message, Program>
+impl<Message, Program, Backend> Widget<Message> for App<Message, Program, Backend>
 where
-    Message: Clone,
+    Message: Clone + Send + 'static,
     Program: for<'a> self::Program<'a, Message>,
+    Backend: self::Backend,
 {
     #[allow(unused_variables)]
     fn render(&self, area: Area, buffer: &mut Buffer, theme: &Theme) {}
@@ -234,3 +575,348 @@ where
     #[allow(unused_variables)]
     fn layout(&mut self, viewport: Area) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    };
+
+    use crate::{
+        backend::test_backend::TestBackend,
+        buffer::buffer::Buffer,
+        command::Command,
+        geometry::{area::Area, size::Size},
+        horizontal,
+        shell::Shell,
+        style::{color::Color, theme::Theme},
+        vertical,
+        widget::{element::Element, widget::Widget},
+        widgets::{
+            button::button, container::container, horizontal::Horizontal, label::label,
+            select::select, vertical::Vertical,
+        },
+    };
+
+    use super::{App, Program};
+
+    #[derive(Clone)]
+    enum Message {
+        Increase,
+        Decrease,
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        count: i64,
+    }
+
+    impl<'a> Program<'a, Message> for Counter {
+        fn update(&mut self, message: Message) -> Command<Message> {
+            match message {
+                Message::Increase => self.count += 1,
+                Message::Decrease => self.count -= 1,
+            }
+            Command::none()
+        }
+
+        fn view(&self) -> Element<'a, Message> {
+            vertical![
+                button("Increase").on_click(Message::Increase).into(),
+                label(self.count.to_string()).into(),
+                button("Decrease").on_click(Message::Decrease).into(),
+            ]
+            .into()
+        }
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn headless() {
+        let mut app = App::with_backend(Counter::default(), TestBackend::new(12, 7));
+        app.step().unwrap();
+        assert_eq!(
+            app.backend().buffer().text(),
+            [
+                "▔▔▔▔▔▔▔▔▔▔▔▔",
+                "  Increase",
+                "▁▁▁▁▁▁▁▁▁▁▁▁",
+                "0",
+                "▔▔▔▔▔▔▔▔▔▔▔▔",
+                "  Decrease",
+                "▁▁▁▁▁▁▁▁▁▁▁▁",
+            ]
+            .join("\n")
+        );
+
+        // Focus the first button with the keyboard and press it.
+        app.backend_mut()
+            .push_event(key(KeyCode::Tab))
+            .push_event(key(KeyCode::Enter));
+        app.step().unwrap();
+        app.step().unwrap();
+        assert_eq!(app.program().count, 1);
+        assert!(app
+            .backend()
+            .buffer()
+            .styled_text()
+            .contains("  Increase\n  0..2 bg=Background\n  2..10 fg=Focus bg=Background"));
+        assert!(app.backend().buffer().text().contains("\n1\n"));
+
+        // Click the second button twice.
+        app.backend_mut()
+            .push_event(mouse(MouseEventKind::Down(MouseButton::Left), 1, 5))
+            .push_event(mouse(MouseEventKind::Up(MouseButton::Left), 1, 5))
+            .push_event(mouse(MouseEventKind::Down(MouseButton::Left), 1, 5))
+            .push_event(mouse(MouseEventKind::Up(MouseButton::Left), 1, 5));
+        app.step().unwrap();
+        app.step().unwrap();
+        assert_eq!(app.program().count, -1);
+        assert!(app.backend().buffer().text().contains("\n-1\n"));
+    }
+
+    #[test]
+    fn quit() {
+        let mut app = App::with_backend(Counter::default(), TestBackend::new(12, 7));
+        app.running = true;
+        // Only pressing the quit key stops the app, not releasing or repeating it.
+        for kind in [KeyEventKind::Release, KeyEventKind::Repeat] {
+            let event = KeyEvent::new_with_kind(KeyCode::Char('q'), KeyModifiers::NONE, kind);
+            app.backend_mut().push_event(Event::Key(event));
+            app.step().unwrap();
+            assert!(app.running);
+        }
+        app.backend_mut().push_event(key(KeyCode::Char('q')));
+        app.step().unwrap();
+        assert!(!app.running);
+    }
+
+    #[derive(Clone)]
+    enum Dialog {
+        Open,
+        Answer(bool),
+    }
+
+    #[derive(Default)]
+    struct Confirm {
+        opened: usize,
+        open: bool,
+        answer: Option<bool>,
+    }
+
+    impl<'a> Program<'a, Dialog> for Confirm {
+        fn update(&mut self, message: Dialog) -> Command<Dialog> {
+            match message {
+                Dialog::Open => {
+                    self.opened += 1;
+                    self.open = true;
+                }
+                Dialog::Answer(answer) => {
+                    self.open = false;
+                    self.answer = Some(answer);
+                }
+            }
+            Command::none()
+        }
+
+        fn view(&self) -> Element<'a, Dialog> {
+            button("Delete").on_click(Dialog::Open).into()
+        }
+
+        fn modal(&self) -> Option<Element<'a, Dialog>> {
+            self.open.then(|| {
+                container(horizontal![
+                    button("Yes").on_click(Dialog::Answer(true)).into(),
+                    button("No").on_click(Dialog::Answer(false)).into(),
+                ])
+                .title("Sure?")
+                .into()
+            })
+        }
+    }
+
+    fn background(buffer: &Buffer, x: u16, y: u16) -> Option<Color> {
+        buffer.get(x, y).style().background
+    }
+
+    #[test]
+    fn modal() {
+        let mut app = App::with_backend(Confirm::default(), TestBackend::new(20, 7));
+        app.backend_mut()
+            .push_event(key(KeyCode::Tab))
+            .push_event(key(KeyCode::Enter));
+        app.step().unwrap();
+        app.step().unwrap();
+        assert!(app.program().open);
+        assert_eq!(
+            app.backend().buffer().text(),
+            [
+                "▔▔▔▔▔▔▔▔▔▔",
+                "  ┌Sure?────────┐",
+                "▁▁│▔▔▔▔▔▔▔▔▔▔▔▔▔│",
+                "  │  Yes    No  │",
+                "  │▁▁▁▁▁▁▁▁▁▁▁▁▁│",
+                "  └─────────────┘",
+                "",
+            ]
+            .join("\n")
+        );
+        // The view is dimmed, but not the modal.
+        let theme = Theme::TOKYO_NIGHT;
+        let mut dimmed = theme.background;
+        dimmed.alpha_on(0x60, theme.background.darker(0.5));
+        let buffer = app.backend().buffer();
+        assert_eq!(background(buffer, 19, 6), Some(Color::RGB(dimmed)));
+        assert_eq!(background(buffer, 8, 3), Some(Color::Background));
+
+        // The view does not get the events, and the focus cycles in the modal.
+        app.backend_mut()
+            .push_event(mouse(MouseEventKind::Down(MouseButton::Left), 0, 1))
+            .push_event(mouse(MouseEventKind::Up(MouseButton::Left), 0, 1))
+            .push_event(key(KeyCode::Tab))
+            .push_event(key(KeyCode::Tab))
+            .push_event(key(KeyCode::Tab))
+            .push_event(key(KeyCode::Enter));
+        app.step().unwrap();
+        app.step().unwrap();
+        assert_eq!(app.program().opened, 1);
+        assert_eq!(app.program().answer, Some(false));
+        assert_eq!(
+            app.backend().buffer().text(),
+            "▔▔▔▔▔▔▔▔▔▔\n  Delete\n▁▁▁▁▁▁▁▁▁▁\n\n\n\n"
+        );
+
+        // The view gets its focus back once the modal is dismissed.
+        app.backend_mut().push_event(key(KeyCode::Enter));
+        app.step().unwrap();
+        assert_eq!(app.program().opened, 2);
+    }
+
+    #[derive(Clone)]
+    enum Pick {
+        Color(usize),
+        Ok,
+    }
+
+    #[derive(Default)]
+    struct Picker {
+        color: usize,
+        ok: bool,
+    }
+
+    impl<'a> Program<'a, Pick> for Picker {
+        fn update(&mut self, message: Pick) -> Command<Pick> {
+            match message {
+                Pick::Color(color) => self.color = color,
+                Pick::Ok => self.ok = true,
+            }
+            Command::none()
+        }
+
+        fn view(&self) -> Element<'a, Pick> {
+            vertical![
+                select(["Red", "Green", "Blue"], Some(self.color))
+                    .on_select(Pick::Color)
+                    .into(),
+                button("OK").on_click(Pick::Ok).into(),
+            ]
+            .into()
+        }
+    }
+
+    #[test]
+    fn overlay() {
+        let mut app = App::with_backend(Picker::default(), TestBackend::new(10, 5));
+        app.backend_mut()
+            .push_event(mouse(MouseEventKind::Down(MouseButton::Left), 0, 0));
+        app.step().unwrap();
+        app.step().unwrap();
+        // The popup is drawn above the button, outside of the bounds of the dropdown.
+        assert_eq!(app.backend().buffer().text(), "Red   ▾\nRed\nGreen\nBlue\n");
+
+        // The popup gets the click before the button below it.
+        app.backend_mut()
+            .push_event(mouse(MouseEventKind::Down(MouseButton::Left), 1, 2))
+            .push_event(mouse(MouseEventKind::Up(MouseButton::Left), 1, 2));
+        app.step().unwrap();
+        app.step().unwrap();
+        assert_eq!(app.program().color, 1);
+        assert!(!app.program().ok);
+        assert_eq!(
+            app.backend().buffer().text(),
+            "Green ▾\n▔▔▔▔▔▔\n  OK\n▁▁▁▁▁▁\n"
+        );
+    }
+
+    /// Shows an overlay below itself and counts the mouse events it gets.
+    struct Probe {
+        events: Rc<Cell<usize>>,
+    }
+
+    impl Widget<()> for Probe {
+        fn render(&self, _area: Area, _buffer: &mut Buffer, _theme: &Theme) {}
+
+        fn process_event(&mut self, event: Event, _shell: &mut Shell<()>) {
+            if let Event::Mouse(_) = event {
+                self.events.set(self.events.get() + 1);
+            }
+        }
+
+        fn size(&self) -> Size {
+            Size::new(4, 1)
+        }
+
+        fn layout(&mut self, _viewport: Area) {}
+
+        fn overlay(&mut self, _viewport: Area) -> Option<Area> {
+            Some(Area::new(0, 1, 4, 1))
+        }
+    }
+
+    struct Probed {
+        events: Rc<Cell<usize>>,
+    }
+
+    impl<'a> Program<'a, ()> for Probed {
+        fn update(&mut self, _message: ()) -> Command<()> {
+            Command::none()
+        }
+
+        fn view(&self) -> Element<'a, ()> {
+            Element::new(Probe {
+                events: self.events.clone(),
+            })
+        }
+    }
+
+    #[test]
+    fn overlay_events_once() {
+        let events = Rc::new(Cell::new(0));
+        let program = Probed {
+            events: events.clone(),
+        };
+        let mut app = App::with_backend(program, TestBackend::new(10, 5));
+        app.step().unwrap();
+        // Inside the overlay, then outside of it.
+        app.backend_mut()
+            .push_event(mouse(MouseEventKind::Down(MouseButton::Left), 1, 1))
+            .push_event(mouse(MouseEventKind::Down(MouseButton::Left), 1, 3));
+        app.step().unwrap();
+        assert_eq!(events.get(), 2);
+    }
+}
@@ -1,3 +1,5 @@
+use std::any::Any;
+
 use crossterm::event::Event;
 
 use crate::{
@@ -11,14 +13,86 @@ use crate::{
 pub trait Widget<Message> {
     fn render(&self, area: Area, buffer: &mut Buffer, theme: &Theme);
 
+    /// Process an event.
+    ///
+    /// Events are propagated from the deepest and topmost widget to the root:
+    /// containers pass the event to their children in the reverse order of rendering
+    /// and stop as soon as [`Shell::is_event_captured`] returns true. Call
+    /// [`Shell::capture_event`] to stop the event from reaching any other widget.
     fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {}
 
     /// Returns the preferred size of the widget.
     fn size(&self) -> Size;
 
+    /// Returns the height of the widget laid out in `width` columns, for the widgets
+    /// whose height depends on their width, like a wrapped text. Containers ask it once
+    /// they know the width of a child, before placing it.
+    fn height_for_width(&self, width: u16) -> u16 {
+        self.size().height
+    }
+
     fn size_hint(&self) -> Size<Length> {
         Size::preferred()
     }
 
     fn layout(&mut self, viewport: Area);
+
+    /// Returns whether the widget can receive the keyboard focus.
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    /// Gives or takes the keyboard focus. Only called on focusable widgets.
+    fn set_focused(&mut self, focused: bool) {}
+
+    /// Returns whether the widget has the keyboard focus.
+    fn is_focused(&self) -> bool {
+        false
+    }
+
+    /// Takes the internal state out of the widget, e.g. a cursor position.
+    /// Since the element tree is rebuilt on every frame, the app keeps the state and
+    /// gives it back through [`Self::restore`] to the widget at the same place of the
+    /// next tree. The widget is dropped right after.
+    fn take_state(&mut self) -> Option<Box<dyn Any>> {
+        None
+    }
+
+    /// Restores the internal state returned by [`Self::take_state`].
+    /// The state may come from another type of widget, so it should be downcasted.
+    fn restore(&mut self, state: Box<dyn Any>) {}
+
+    /// Lays out the overlay of the widget if it is shown, e.g. the popup of a dropdown,
+    /// and returns its area. Overlays are drawn by the app above the whole tree, even
+    /// outside of the bounds of the widget, and get the mouse events hitting them before
+    /// the tree.
+    ///
+    /// `viewport` is the area of the root, where the overlay should fit. Both areas are
+    /// in the coordinates of the bounds of the widget. Called after [`Self::layout`].
+    fn overlay(&mut self, viewport: Area) -> Option<Area> {
+        None
+    }
+
+    /// Render the overlay returned by [`Self::overlay`], moved to `area` of the buffer.
+    fn render_overlay(&self, area: Area, buffer: &mut Buffer, theme: &Theme) {}
+
+    /// Returns the position of the origin of the coordinates of the children in the
+    /// coordinates of the widget. Only containers laying out their children in their
+    /// own coordinates, like a scrolled content, need to implement this.
+    fn children_origin(&self) -> (i32, i32) {
+        (0, 0)
+    }
+
+    /// Returns the children of the widget, in the order of rendering.
+    /// Containers must implement this, so that the app can walk the whole element tree.
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget<Message>> {
+        Vec::new()
+    }
+
+    /// Returns all the children of the widget, including the hidden ones left out of the
+    /// layout and the focus chain, e.g. the inactive tabs. The app keeps the internal
+    /// states of all of them, so that hidden children get theirs back once shown.
+    fn all_children_mut(&mut self) -> Vec<&mut dyn Widget<Message>> {
+        self.children_mut()
+    }
 }
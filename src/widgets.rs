@@ -1,4 +1,22 @@
 pub mod button;
+pub mod checkbox;
+pub mod container;
+pub mod horizontal;
 pub mod label;
+pub mod list;
 pub mod padding;
+pub mod progress;
+pub mod radio;
+mod scroll;
+pub mod scrollable;
+pub mod select;
+pub mod stack;
+pub mod table;
+pub mod tabs;
+#[cfg(test)]
+mod testing;
+pub mod text_area;
+pub mod text_input;
+pub mod toggle;
+pub mod tree;
 pub mod vertical;
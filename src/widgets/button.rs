@@ -1,39 +1,70 @@
-use crossterm::event::{Event, MouseButton, MouseEventKind};
+use std::{any::Any, collections::HashMap};
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind};
 
 use crate::{
     buffer::buffer::Buffer,
     geometry::{area::Area, length::Length, size::Size, spacing::Spacing},
     shell::Shell,
     style::{color::Color, style::Style, theme::Theme},
-    text::Text,
+    text::StyledText,
     widget::{element::Element, widget::Widget},
 };
 
+/// The interaction state of a [`Button`], which selects its style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ButtonState {
+    Idle,
+    /// The mouse is over the button.
+    Hovered,
+    /// The left mouse button has been pressed on the button and is still over it.
+    Pressed,
+    /// The button has the keyboard focus.
+    Focused,
+    /// The button ignores events, see [`Button::disabled`].
+    Disabled,
+}
+
+/// Where the mouse is relative to a [`Button`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct State {
+    hovering: bool,
+    /// Whether the left mouse button has been pressed on the button and not released.
+    pressed: bool,
+}
+
 pub struct Button<Message: Clone> {
     padding: Spacing,
-    content: Text,
+    content: StyledText,
     content_style: Style,
+    /// The style overrides of the states.
+    styles: HashMap<ButtonState, Style>,
     background: Option<Color>,
     bounds: Area,
     on_click: Option<Message>,
-    hovering: bool,
+    disabled: bool,
+    state: State,
+    focused: bool,
     size_hint: Size<Length>,
 }
 
-pub fn button<'a, Message: 'a + Clone>(content: impl Into<String>) -> Button<Message> {
+pub fn button<'a, Message: 'a + Clone>(content: impl Into<StyledText>) -> Button<Message> {
     Button::new(content)
 }
 
 impl<Message: Clone> Button<Message> {
-    pub fn new(content: impl Into<String>) -> Self {
+    pub fn new(content: impl Into<StyledText>) -> Self {
         Button {
             padding: Spacing::axes(1, 2),
-            content: Text::new(content),
+            content: content.into(),
             content_style: Style::new().foreground(Color::Text),
+            styles: HashMap::new(),
             background: Some(Color::Background),
             bounds: Area::zeros(),
             on_click: None,
-            hovering: false,
+            disabled: false,
+            state: State::default(),
+            focused: false,
             size_hint: Size::preferred(),
         }
     }
@@ -43,8 +74,8 @@ impl<Message: Clone> Button<Message> {
         self
     }
 
-    pub fn content(mut self, content: impl Into<String>) -> Self {
-        self.content.set(content);
+    pub fn content(mut self, content: impl Into<StyledText>) -> Self {
+        self.content = content.into();
         self
     }
 
@@ -62,6 +93,64 @@ impl<Message: Clone> Button<Message> {
         self.size_hint.height = height;
         self
     }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Override the style of the content in a state. The style is patched on the one
+    /// derived from the theme, see [`Style::patch`].
+    pub fn style(mut self, state: ButtonState, style: Style) -> Self {
+        self.styles.insert(state, style);
+        self
+    }
+
+    /// Grey out the button. A disabled button is not focusable and produces no messages.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Returns the current interaction state of the button.
+    pub fn state(&self) -> ButtonState {
+        if self.disabled {
+            ButtonState::Disabled
+        } else if self.state.pressed && self.state.hovering {
+            ButtonState::Pressed
+        } else if self.state.hovering {
+            ButtonState::Hovered
+        } else if self.focused {
+            ButtonState::Focused
+        } else {
+            ButtonState::Idle
+        }
+    }
+
+    /// Get the style of the content in a state, derived from the theme and the overrides.
+    fn state_style(&self, state: ButtonState, theme: &Theme) -> Style {
+        let background = self.background.map(|background| background.on_theme(theme));
+        let style = match state {
+            ButtonState::Idle => self.content_style,
+            ButtonState::Hovered => match background {
+                Some(background) => self.content_style.background(background.brighter(0.1)),
+                None => self.content_style,
+            },
+            ButtonState::Pressed => {
+                let style = self.content_style.foreground(Color::Primary);
+                match background {
+                    Some(background) => style.background(background.darker(0.1)),
+                    None => style,
+                }
+            }
+            ButtonState::Focused => self.content_style.foreground(Color::Focus),
+            ButtonState::Disabled => self.content_style.foreground(Color::Disabled),
+        };
+        match self.styles.get(&state) {
+            Some(overrides) => style.patch(*overrides),
+            None => style,
+        }
+    }
 }
 
 impl<Message: Clone> Widget<Message> for Button<Message> {
@@ -70,36 +159,74 @@ impl<Message: Clone> Widget<Message> for Button<Message> {
     }
 
     fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
-        if shell.is_event_captured() {
+        if shell.is_event_captured() || self.disabled {
             return;
         }
-        if let Event::Mouse(event) = event {
-            if self.bounds.contains(event.column, event.row) {
-                self.hovering = true;
-                if let MouseEventKind::Down(button) = event.kind {
-                    if button == MouseButton::Left {
+        if let Event::Key(event) = event {
+            if self.focused
+                && event.kind == KeyEventKind::Press
+                && matches!(event.code, KeyCode::Enter | KeyCode::Char(' '))
+            {
+                if let Some(message) = self.on_click.clone() {
+                    shell.push(message);
+                }
+                shell.capture_event();
+            }
+        } else if let Event::Mouse(event) = event {
+            let inside = self.bounds.contains(event.column, event.row);
+            self.state.hovering = inside;
+            match event.kind {
+                MouseEventKind::Down(MouseButton::Left) if inside => {
+                    self.state.pressed = true;
+                    shell.capture_event();
+                }
+                // Only a release over the button clicks it, so that a press can be
+                // cancelled by moving the mouse away.
+                MouseEventKind::Up(MouseButton::Left) if self.state.pressed => {
+                    self.state.pressed = false;
+                    if inside {
                         if let Some(message) = self.on_click.clone() {
                             shell.push(message);
                         }
+                        shell.capture_event();
                     }
                 }
+                _ => {}
             }
         }
     }
 
     fn render(&self, area: Area, buffer: &mut Buffer, theme: &Theme) {
-        buffer
-            .render_background(area, self.background)
-            .render_string(
+        let state = self.state();
+        let content_style = self.state_style(state, theme);
+        let background = content_style.background.or(self.background);
+        buffer.render_background(area, background);
+        if state == ButtonState::Disabled {
+            // Grey out the whole content, whatever the styles of the spans.
+            buffer.render_string(
                 self.content.raw(),
-                self.content_style,
+                content_style,
                 area.shrink(self.padding),
                 true,
             );
+        } else {
+            buffer.render_spans(
+                &self.content,
+                content_style,
+                area.shrink(self.padding),
+                true,
+            );
+        }
         if !area.is_empty() {
-            if let Some(background) = self.background {
-                let highlight = background.on_theme(theme).brighter(0.2);
-                let shadow = background.on_theme(theme).darker(0.2);
+            if let Some(background) = background {
+                let background = background.on_theme(theme);
+                let (highlight, shadow) = match state {
+                    ButtonState::Disabled => (background, background),
+                    // Swap the bevel so that the button looks sunken.
+                    ButtonState::Pressed => (background.darker(0.2), background.brighter(0.2)),
+                    _ if self.focused => (theme.focus, theme.focus),
+                    _ => (background.brighter(0.2), background.darker(0.2)),
+                };
                 buffer
                     .render_string(
                         "▁".repeat(area.width as usize),
@@ -127,6 +254,28 @@ impl<Message: Clone> Widget<Message> for Button<Message> {
     fn size_hint(&self) -> Size<Length> {
         self.size_hint
     }
+
+    fn is_focusable(&self) -> bool {
+        !self.disabled
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn take_state(&mut self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.state))
+    }
+
+    fn restore(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<State>() {
+            self.state = *state;
+        }
+    }
 }
 
 impl<'a, Message: Clone + 'a> From<Button<Message>> for Element<'a, Message> {
@@ -134,3 +283,62 @@ impl<'a, Message: Clone + 'a> From<Button<Message>> for Element<'a, Message> {
         Self::new(button)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    use crate::{geometry::area::Area, shell::Shell, widget::widget::Widget};
+
+    use super::{Button, ButtonState};
+
+    fn mouse(button: &mut Button<i32>, kind: MouseEventKind, column: u16) -> Shell<i32> {
+        let mut shell = Shell::new();
+        let event = MouseEvent {
+            kind,
+            column,
+            row: 1,
+            modifiers: KeyModifiers::NONE,
+        };
+        button.process_event(Event::Mouse(event), &mut shell);
+        shell
+    }
+
+    #[test]
+    fn click() {
+        let left = MouseButton::Left;
+        let mut button = Button::new("Ok").on_click(1);
+        button.layout(Area::new(0, 0, 6, 3));
+
+        mouse(&mut button, MouseEventKind::Moved, 2);
+        assert_eq!(button.state(), ButtonState::Hovered);
+        let shell = mouse(&mut button, MouseEventKind::Down(left), 2);
+        assert!(shell.messages().is_empty());
+        assert!(shell.is_event_captured());
+        assert_eq!(button.state(), ButtonState::Pressed);
+        let shell = mouse(&mut button, MouseEventKind::Up(left), 3);
+        assert_eq!(shell.messages(), &vec![1]);
+        assert_eq!(button.state(), ButtonState::Hovered);
+
+        // Releasing outside cancels the click.
+        mouse(&mut button, MouseEventKind::Down(left), 2);
+        mouse(&mut button, MouseEventKind::Drag(left), 10);
+        assert_eq!(button.state(), ButtonState::Idle);
+        let shell = mouse(&mut button, MouseEventKind::Up(left), 10);
+        assert!(shell.messages().is_empty());
+    }
+
+    #[test]
+    fn disabled() {
+        let left = MouseButton::Left;
+        let mut button = Button::new("Ok").on_click(1).disabled(true);
+        button.layout(Area::new(0, 0, 6, 3));
+        assert!(!button.is_focusable());
+
+        mouse(&mut button, MouseEventKind::Down(left), 2);
+        let shell = mouse(&mut button, MouseEventKind::Up(left), 2);
+        assert!(shell.messages().is_empty());
+        assert!(!shell.is_event_captured());
+        assert_eq!(button.state(), ButtonState::Disabled);
+    }
+}
@@ -0,0 +1,228 @@
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind};
+
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::{area::Area, length::Length, size::Size},
+    shell::Shell,
+    style::{color::Color, style::Style, theme::Theme},
+    text::StyledText,
+    widget::{element::Element, widget::Widget},
+};
+
+/// The number of columns of the indicator, e.g. the box, and the space before the label.
+const BOX_WIDTH: u16 = 4;
+
+/// The glyphs of an indicator, from whether it is checked and whether only ASCII can be
+/// drawn.
+pub(crate) type Glyph = fn(bool, bool) -> &'static str;
+
+/// A box checked with Space or a click, followed by a label.
+pub struct Checkbox<Message> {
+    label: StyledText,
+    checked: bool,
+    on_toggle: Option<Box<dyn Fn(bool) -> Message>>,
+    glyph: Glyph,
+    /// The colors of the indicator when checked and unchecked.
+    colors: (Color, Color),
+    style: Style,
+    background: Option<Color>,
+    bounds: Area,
+    focused: bool,
+    size_hint: Size<Length>,
+}
+
+pub fn checkbox<Message>(label: impl Into<StyledText>, checked: bool) -> Checkbox<Message> {
+    Checkbox::new(label, checked)
+}
+
+impl<Message> Checkbox<Message> {
+    pub fn new(label: impl Into<StyledText>, checked: bool) -> Self {
+        Self {
+            label: label.into(),
+            checked,
+            on_toggle: None,
+            glyph: box_glyph,
+            colors: (Color::Primary, Color::Text),
+            style: Style::new().foreground(Color::Text),
+            background: None,
+            bounds: Area::zeros(),
+            focused: false,
+            size_hint: Size::preferred(),
+        }
+    }
+
+    /// Set the message produced with the new checked state when the user toggles the
+    /// checkbox.
+    pub fn on_toggle(mut self, on_toggle: impl Fn(bool) -> Message + 'static) -> Self {
+        self.on_toggle = Some(Box::new(on_toggle));
+        self
+    }
+
+    /// Set the base style of the label.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.size_hint.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.size_hint.height = height.into();
+        self
+    }
+
+    /// Replace the box with another indicator three columns wide, e.g. a switch.
+    pub(crate) fn indicator(mut self, glyph: Glyph, colors: (Color, Color)) -> Self {
+        self.glyph = glyph;
+        self.colors = colors;
+        self
+    }
+}
+
+fn box_glyph(checked: bool, ascii: bool) -> &'static str {
+    match (checked, ascii) {
+        (true, false) => "[✓]",
+        (true, true) => "[x]",
+        (false, _) => "[ ]",
+    }
+}
+
+impl<Message> Widget<Message> for Checkbox<Message> {
+    fn layout(&mut self, viewport: Area) {
+        self.bounds = viewport;
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() {
+            return;
+        }
+        let toggled = match event {
+            Event::Key(event) => {
+                self.focused
+                    && event.kind == KeyEventKind::Press
+                    && event.code == KeyCode::Char(' ')
+            }
+            Event::Mouse(event) => {
+                event.kind == MouseEventKind::Down(MouseButton::Left)
+                    && self.bounds.contains(event.column, event.row)
+            }
+            _ => false,
+        };
+        if !toggled {
+            return;
+        }
+        shell.capture_event();
+        if !self.focused {
+            self.focused = true;
+            shell.request_focus();
+        }
+        if let Some(on_toggle) = &self.on_toggle {
+            shell.push(on_toggle(!self.checked));
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, _theme: &Theme) {
+        buffer.render_background(area, self.background);
+        let glyph_style = Style::new().foreground(if self.checked {
+            self.colors.0
+        } else {
+            self.colors.1
+        });
+        let mut style = self.style;
+        if self.focused {
+            style = style.foreground(Color::Focus);
+        }
+        let glyph = (self.glyph)(self.checked, buffer.is_ascii());
+        buffer.render_string(glyph, glyph_style, area, false);
+        let label = Area::new(
+            area.x.saturating_add(BOX_WIDTH),
+            area.y,
+            area.width.saturating_sub(BOX_WIDTH),
+            area.height,
+        );
+        buffer.render_spans(&self.label, style, label, false);
+    }
+
+    fn size(&self) -> Size {
+        let label = self.label.size();
+        Size::new(BOX_WIDTH + label.width, label.height.max(1))
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+}
+
+impl<'a, Message: 'a> From<Checkbox<Message>> for Element<'a, Message> {
+    fn from(value: Checkbox<Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+
+    use crate::{
+        buffer::buffer::Buffer,
+        geometry::area::Area,
+        shell::Shell,
+        style::{color::ColorSystem, theme::Theme},
+        widget::widget::Widget,
+    };
+
+    use super::checkbox;
+
+    #[test]
+    fn toggle() {
+        let mut checkbox = checkbox("Dark mode", true).on_toggle(|checked| checked);
+        let area = Area::from_wh(14, 1);
+        checkbox.layout(area);
+        let mut buffer = Buffer::new(14, 1);
+        checkbox.render(area, &mut buffer, &Theme::TOKYO_NIGHT);
+        assert_eq!(buffer.text(), "[✓] Dark mode");
+        buffer.set_color_system(ColorSystem::LegacyWindows);
+        checkbox.render(area, &mut buffer, &Theme::TOKYO_NIGHT);
+        assert_eq!(buffer.text(), "[x] Dark mode");
+
+        // Space only toggles a focused checkbox, and a click focuses it.
+        let space = Event::Key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        let mut shell = Shell::new();
+        checkbox.process_event(space.clone(), &mut shell);
+        assert!(shell.messages().is_empty());
+        let click = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 8,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        });
+        checkbox.process_event(click, &mut shell);
+        assert_eq!(shell.messages(), &vec![false]);
+        assert!(shell.is_focus_requested());
+        let mut shell = Shell::new();
+        checkbox.process_event(space, &mut shell);
+        assert_eq!(shell.messages(), &vec![false]);
+    }
+}
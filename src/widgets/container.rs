@@ -0,0 +1,341 @@
+use crossterm::event::Event;
+
+use crate::{
+    buffer::{buffer::Buffer, pixel::Pixel},
+    geometry::{area::Area, length::Length, size::Size, spacing::Spacing},
+    shell::Shell,
+    style::{color::Color, style::Style, theme::Theme},
+    text::{
+        layout::{Alignment, TextLayout},
+        StyledText,
+    },
+    widget::{element::Element, widget::Widget},
+};
+
+/// The characters drawing a border.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorderSet {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+}
+
+impl BorderSet {
+    pub const PLAIN: Self = Self {
+        top_left: '┌',
+        top_right: '┐',
+        bottom_left: '└',
+        bottom_right: '┘',
+        horizontal: '─',
+        vertical: '│',
+    };
+
+    pub const ROUNDED: Self = Self {
+        top_left: '╭',
+        top_right: '╮',
+        bottom_left: '╰',
+        bottom_right: '╯',
+        ..Self::PLAIN
+    };
+
+    pub const DOUBLE: Self = Self {
+        top_left: '╔',
+        top_right: '╗',
+        bottom_left: '╚',
+        bottom_right: '╝',
+        horizontal: '═',
+        vertical: '║',
+    };
+
+    pub const THICK: Self = Self {
+        top_left: '┏',
+        top_right: '┓',
+        bottom_left: '┗',
+        bottom_right: '┛',
+        horizontal: '━',
+        vertical: '┃',
+    };
+
+    pub const ASCII: Self = Self {
+        top_left: '+',
+        top_right: '+',
+        bottom_left: '+',
+        bottom_right: '+',
+        horizontal: '-',
+        vertical: '|',
+    };
+}
+
+/// A box around an element, with an optional border, title and footer.
+pub struct Container<'a, Message> {
+    element: Element<'a, Message>,
+    border: Option<BorderSet>,
+    border_color: Color,
+    title: Option<StyledText>,
+    title_alignment: Alignment,
+    footer: Option<StyledText>,
+    footer_alignment: Alignment,
+    padding: Spacing,
+    background: Option<Color>,
+    /// The size hints set by the user, otherwise the ones of the element are used.
+    width: Option<Length>,
+    height: Option<Length>,
+}
+
+pub fn container<'a, Message>(element: impl Into<Element<'a, Message>>) -> Container<'a, Message> {
+    Container::new(element)
+}
+
+impl<'a, Message> Container<'a, Message> {
+    pub fn new(element: impl Into<Element<'a, Message>>) -> Self {
+        Self {
+            element: element.into(),
+            border: Some(BorderSet::PLAIN),
+            border_color: Color::Border,
+            title: None,
+            title_alignment: Alignment::Left,
+            footer: None,
+            footer_alignment: Alignment::Right,
+            padding: Spacing::zeros(),
+            background: None,
+            width: None,
+            height: None,
+        }
+    }
+
+    /// Set the characters of the border, or remove it with `None`.
+    pub fn border(mut self, border: Option<BorderSet>) -> Self {
+        self.border = border;
+        self
+    }
+
+    pub fn border_color(mut self, color: impl Into<Color>) -> Self {
+        self.border_color = color.into();
+        self
+    }
+
+    /// Set the title drawn on the top border. Only the first line is drawn, and it is
+    /// truncated with `…` if it does not fit.
+    pub fn title(mut self, title: impl Into<StyledText>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn title_alignment(mut self, alignment: Alignment) -> Self {
+        self.title_alignment = alignment;
+        self
+    }
+
+    /// Set the footer drawn on the bottom border, like the title.
+    pub fn footer(mut self, footer: impl Into<StyledText>) -> Self {
+        self.footer = Some(footer.into());
+        self
+    }
+
+    pub fn footer_alignment(mut self, alignment: Alignment) -> Self {
+        self.footer_alignment = alignment;
+        self
+    }
+
+    /// Set the space between the border and the element.
+    pub fn padding(mut self, padding: impl Into<Spacing>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = Some(width.into());
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = Some(height.into());
+        self
+    }
+
+    /// The space taken by the border and the padding on each side.
+    fn frame(&self) -> Spacing {
+        let border = self.border.is_some() as u16;
+        Spacing::new(
+            self.padding.top + border,
+            self.padding.right + border,
+            self.padding.bottom + border,
+            self.padding.left + border,
+        )
+    }
+
+    fn render_border(&self, area: Area, buffer: &mut Buffer, border: BorderSet) {
+        if area.width < 2 || area.height < 2 {
+            return;
+        }
+        let style = Style::new().foreground(self.border_color);
+        let (left, top) = (area.x, area.y);
+        let (right, bottom) = (area.x + area.width - 1, area.y + area.height - 1);
+        let mut render = |x, y, char| {
+            buffer.render_pixel(x, y, Pixel::from_char(char).set_style(style));
+        };
+        for x in left + 1..right {
+            render(x, top, border.horizontal);
+            render(x, bottom, border.horizontal);
+        }
+        for y in top + 1..bottom {
+            render(left, y, border.vertical);
+            render(right, y, border.vertical);
+        }
+        render(left, top, border.top_left);
+        render(right, top, border.top_right);
+        render(left, bottom, border.bottom_left);
+        render(right, bottom, border.bottom_right);
+    }
+
+    /// Render a title or a footer on a row of the border, between the corners.
+    fn render_label(
+        &self,
+        text: &StyledText,
+        alignment: Alignment,
+        area: Area,
+        y: u16,
+        buffer: &mut Buffer,
+    ) {
+        let inset = self.border.is_some() as u16;
+        let line = StyledText::new(text.lines.first().cloned());
+        let layout = TextLayout::new()
+            .wrap(false)
+            .truncate(true)
+            .alignment(alignment);
+        let width = area.width.saturating_sub(inset * 2);
+        buffer.render_text(
+            &line,
+            Style::new().foreground(Color::Text),
+            Area::new(area.x + inset, y, width, 1),
+            &layout,
+        );
+    }
+}
+
+impl<Message> Widget<Message> for Container<'_, Message> {
+    fn layout(&mut self, viewport: Area) {
+        let frame = self.frame();
+        self.element.widget_mut().layout(viewport.shrink(frame));
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, theme: &Theme) {
+        buffer.render_background(area, self.background);
+        if area.is_empty() {
+            return;
+        }
+        if let Some(border) = self.border {
+            self.render_border(area, buffer, border);
+        }
+        if let Some(title) = &self.title {
+            self.render_label(title, self.title_alignment, area, area.y, buffer);
+        }
+        if let Some(footer) = &self.footer {
+            let y = area.y + area.height - 1;
+            self.render_label(footer, self.footer_alignment, area, y, buffer);
+        }
+        self.element
+            .widget()
+            .render(area.shrink(self.frame()), buffer, theme);
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() {
+            return;
+        }
+        self.element.widget_mut().process_event(event, shell)
+    }
+
+    fn size(&self) -> Size {
+        let size = self.element.widget().size();
+        let frame = self.frame();
+        Size::new(
+            size.width + frame.left + frame.right,
+            size.height + frame.top + frame.bottom,
+        )
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        // A fixed element keeps its size inside the frame.
+        let frame = self.frame();
+        let hint = self.element.widget().size_hint();
+        let width = match hint.width {
+            Length::Fixed(width) => Length::Fixed(width + frame.left + frame.right),
+            width => width,
+        };
+        let height = match hint.height {
+            Length::Fixed(height) => Length::Fixed(height + frame.top + frame.bottom),
+            height => height,
+        };
+        Size::new(self.width.unwrap_or(width), self.height.unwrap_or(height))
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget<Message>> {
+        vec![self.element.widget_mut()]
+    }
+}
+
+impl<'a, Message: 'a> From<Container<'a, Message>> for Element<'a, Message> {
+    fn from(value: Container<'a, Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::buffer::Buffer,
+        geometry::{area::Area, length::Length, size::Size},
+        style::theme::Theme,
+        text::layout::Alignment,
+        widget::widget::Widget,
+        widgets::label::label,
+    };
+
+    use super::{container, BorderSet, Container};
+
+    fn render(container: &mut Container<()>, width: u16, height: u16) -> String {
+        let mut buffer = Buffer::new(width, height);
+        let area = Area::from_wh(width, height);
+        container.layout(area);
+        container.render(area, &mut buffer, &Theme::TOKYO_NIGHT);
+        buffer.text()
+    }
+
+    #[test]
+    fn border() {
+        let mut block = container(label("Hello"))
+            .border(Some(BorderSet::ROUNDED))
+            .title("Title")
+            .footer("a long footer")
+            .padding((0, 1));
+        assert_eq!(Widget::<()>::size(&block), Size::new(9, 3));
+        assert_eq!(
+            render(&mut block, 11, 3),
+            "╭Title────╮\n│ Hello   │\n╰a long f…╯"
+        );
+
+        let mut block = container(label("Hi"))
+            .border(Some(BorderSet::ASCII))
+            .title("T")
+            .title_alignment(Alignment::Center);
+        assert_eq!(render(&mut block, 7, 3), "+--T--+\n|Hi   |\n+-----+");
+    }
+
+    #[test]
+    fn size_hint() {
+        let block = container(label("Hi").width(Length::Fixed(4))).padding(1);
+        let hint = Widget::<()>::size_hint(&block);
+        assert_eq!(hint, Size::new(Length::Fixed(8), Length::Preferred));
+        let block = block.width(Length::Fill);
+        assert_eq!(Widget::<()>::size_hint(&block).width, Length::Fill);
+    }
+}
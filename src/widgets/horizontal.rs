@@ -0,0 +1,278 @@
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::{area::Area, length::Length, size::Size},
+    shell::Shell,
+    style::{color::Color, theme::Theme},
+    widget::{element::Element, widget::Widget},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerticalAlignment {
+    Start,
+    Center,
+    End,
+}
+
+pub struct Horizontal<'a, Message> {
+    children: Vec<Element<'a, Message>>,
+    alignment: VerticalAlignment,
+    background: Option<Color>,
+    children_bounds: Vec<Area>,
+    preferred_size: Size,
+    size_hint: Size<Length>,
+    spacing: u16,
+}
+
+#[macro_export]
+macro_rules! horizontal {
+    () => {
+        Horizontal::new()
+    };
+    ($($element:expr), + $(,)?) => {
+        Horizontal::with_children(vec![$($element),+])
+    };
+}
+
+impl<Message> Default for Horizontal<'_, Message> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, Message> Horizontal<'a, Message> {
+    pub fn new() -> Self {
+        Self {
+            children: Vec::new(),
+            alignment: VerticalAlignment::Start,
+            background: None,
+            children_bounds: Vec::new(),
+            preferred_size: Size::new(0, 0),
+            size_hint: Size::preferred(),
+            spacing: 0,
+        }
+    }
+
+    pub fn with_children(children: Vec<Element<'a, Message>>) -> Self {
+        let mut children_bounds = Vec::with_capacity(children.len());
+        let mut preferred_size = Size::new(0, 0);
+        for child in children.iter() {
+            children_bounds.push(Area::zeros());
+            preferred_size.width += child.widget().size().width;
+            preferred_size.height = preferred_size.height.max(child.widget().size().height);
+        }
+        Self {
+            children,
+            alignment: VerticalAlignment::Start,
+            background: None,
+            children_bounds,
+            preferred_size,
+            size_hint: Size::preferred(),
+            spacing: 0,
+        }
+    }
+
+    pub fn alignment(mut self, alignment: VerticalAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.size_hint.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.size_hint.height = height.into();
+        self
+    }
+
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    fn total_spacing(&self) -> u16 {
+        self.spacing
+            .saturating_mul((self.children.len() as u16).saturating_sub(1))
+    }
+
+    /// Get the widths of the children laid out in `width` columns.
+    fn children_widths(&self, width: u16) -> Vec<u16> {
+        Length::resolve(
+            width.saturating_sub(self.total_spacing()),
+            self.children
+                .iter()
+                .map(|child| child.widget().size_hint().width)
+                .collect(),
+            self.children
+                .iter()
+                .map(|child| child.widget().size().width)
+                .collect(),
+        )
+    }
+}
+
+impl<Message> Widget<Message> for Horizontal<'_, Message> {
+    fn layout(&mut self, viewport: Area) {
+        let children_widths = self.children_widths(viewport.width);
+        let children_heights: Vec<_> = self
+            .children
+            .iter()
+            .zip(&children_widths)
+            .map(|(child, &width)| match child.widget().size_hint().height {
+                Length::Preferred => child.widget().height_for_width(width).min(viewport.height),
+                Length::Fixed(height) => height.min(viewport.height),
+                _ => viewport.height,
+            })
+            .collect();
+
+        let end_x = viewport.x.saturating_add(viewport.width);
+        let mut x = viewport.x;
+        for (i, (width, height)) in children_widths
+            .into_iter()
+            .zip(children_heights)
+            .enumerate()
+        {
+            let y = match self.alignment {
+                VerticalAlignment::Start => viewport.y,
+                VerticalAlignment::Center => {
+                    viewport.y + viewport.height.saturating_sub(height) / 2
+                }
+                VerticalAlignment::End => viewport.y + viewport.height.saturating_sub(height),
+            };
+            let bounds = Area::new(x, y, width.min(end_x.saturating_sub(x)), height);
+            self.children_bounds[i] = bounds;
+            self.children[i].widget_mut().layout(bounds);
+            x = x.saturating_add(width).saturating_add(self.spacing);
+        }
+    }
+
+    fn process_event(&mut self, event: crossterm::event::Event, shell: &mut Shell<Message>) {
+        for child in self.children.iter_mut().rev() {
+            if shell.is_event_captured() {
+                return;
+            }
+            child.widget_mut().process_event(event.clone(), shell);
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, theme: &Theme) {
+        buffer.render_background(area, self.background);
+        for (child, bounds) in self.children.iter().zip(self.children_bounds.iter()) {
+            child.widget().render(*bounds, buffer, theme);
+        }
+    }
+
+    fn size(&self) -> Size {
+        Size::new(
+            self.preferred_size.width + self.total_spacing(),
+            self.preferred_size.height,
+        )
+    }
+
+    fn height_for_width(&self, width: u16) -> u16 {
+        self.children
+            .iter()
+            .zip(self.children_widths(width))
+            .map(|(child, width)| child.widget().height_for_width(width))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget<Message>> {
+        self.children
+            .iter_mut()
+            .map(|child| child.widget_mut())
+            .collect()
+    }
+}
+
+impl<'a, Message: 'a> From<Horizontal<'a, Message>> for Element<'a, Message> {
+    fn from(value: Horizontal<'a, Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    use crate::{
+        buffer::buffer::Buffer,
+        geometry::{area::Area, length::Length, size::Size},
+        shell::Shell,
+        style::theme::Theme,
+        widget::widget::Widget,
+        widgets::{button::button, label::label},
+    };
+
+    use super::{Horizontal, VerticalAlignment};
+
+    fn render(horizontal: &mut Horizontal<()>, width: u16, height: u16) -> String {
+        let mut buffer = Buffer::new(width, height);
+        let area = Area::from_wh(width, height);
+        horizontal.layout(area);
+        horizontal.render(area, &mut buffer, &Theme::TOKYO_NIGHT);
+        buffer.text()
+    }
+
+    #[test]
+    fn layout() {
+        let mut row = horizontal![
+            label("one\ntwo").into(),
+            label("3").into(),
+            label("four").into()
+        ]
+        .spacing(1);
+        assert_eq!(Widget::<()>::size(&row), Size::new(10, 2));
+        assert_eq!(render(&mut row, 12, 2), "one 3 four\ntwo");
+        // The children are shrunk to the viewport, a label wraps in the width left.
+        assert_eq!(render(&mut row, 7, 2), "one 3 f\ntwo   o");
+
+        let mut row = horizontal![
+            label("a").into(),
+            label("fill").width(Length::Fill).into(),
+            label("b").width(Length::Fixed(2)).into(),
+        ]
+        .alignment(VerticalAlignment::End);
+        assert_eq!(render(&mut row, 10, 2), "\nafill   b");
+        let mut row = row.alignment(VerticalAlignment::Center);
+        assert_eq!(render(&mut row, 10, 3), "\nafill   b\n");
+    }
+
+    #[test]
+    fn events() {
+        let mut row = horizontal![
+            button("One").on_click(1).into(),
+            button("Two").on_click(2).into()
+        ];
+        row.layout(Area::from_wh(20, 1));
+        // The second button starts right after the first one.
+        let width = Widget::<i32>::size(&button::<i32>("One")).width;
+
+        let mut shell = Shell::new();
+        for kind in [
+            MouseEventKind::Down(MouseButton::Left),
+            MouseEventKind::Up(MouseButton::Left),
+        ] {
+            let event = MouseEvent {
+                kind,
+                column: width + 1,
+                row: 0,
+                modifiers: KeyModifiers::NONE,
+            };
+            shell = Shell::new();
+            row.process_event(Event::Mouse(event), &mut shell);
+        }
+        assert_eq!(shell.messages(), &vec![2]);
+    }
+}
@@ -2,43 +2,59 @@ use crate::{
     buffer::buffer::Buffer,
     geometry::{area::Area, length::Length, size::Size},
     style::{color::Color, style::Style, theme::Theme},
-    text::Text,
+    text::{
+        layout::{Alignment, TextLayout},
+        StyledText,
+    },
     widget::{element::Element, widget::Widget},
 };
 
 pub struct Label {
-    text: Text,
+    text: StyledText,
     style: Style,
-    wrap: bool,
+    layout: TextLayout,
     size_hint: Size<Length>,
 }
 
-pub fn label(content: impl Into<String>) -> Label {
+pub fn label(content: impl Into<StyledText>) -> Label {
     Label::new(content)
 }
 
 impl Label {
-    pub fn new(content: impl Into<String>) -> Self {
+    pub fn new(content: impl Into<StyledText>) -> Self {
         Self {
-            text: Text::new(content),
+            text: content.into(),
             style: Style::new().foreground(Color::Text),
-            wrap: true,
+            layout: TextLayout::new(),
             size_hint: Size::preferred(),
         }
     }
 
-    pub fn content(mut self, content: impl Into<String>) -> Self {
-        self.text.set(content);
+    pub fn content(mut self, content: impl Into<StyledText>) -> Self {
+        self.text = content.into();
         self
     }
 
+    /// Set the base style of the content, on which the styles of the spans are patched.
     pub fn style(mut self, style: Style) -> Self {
         self.style = style;
         self
     }
 
+    /// Wrap the lines wider than the label on word boundaries, otherwise cut them.
     pub fn wrap(mut self, wrap: bool) -> Self {
-        self.wrap = wrap;
+        self.layout = self.layout.wrap(wrap);
+        self
+    }
+
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.layout = self.layout.alignment(alignment);
+        self
+    }
+
+    /// End the lines which do not fit in the label with `…`.
+    pub fn truncate(mut self, truncate: bool) -> Self {
+        self.layout = self.layout.truncate(truncate);
         self
     }
 
@@ -55,19 +71,23 @@ impl Label {
 
 impl<Message> Widget<Message> for Label {
     fn render(&self, area: Area, buffer: &mut Buffer, _theme: &Theme) {
-        buffer.render_string(self.text.raw(), self.style, area, self.wrap);
+        buffer.render_text(&self.text, self.style, area, &self.layout);
     }
 
+    /// Returns the size of the text without wrapping.
     fn size(&self) -> Size<u16> {
-        Size::new(self.text.size().width, self.text.size().height)
+        self.layout.measure(&self.text, None)
+    }
+
+    fn height_for_width(&self, width: u16) -> u16 {
+        self.layout.measure(&self.text, Some(width)).height
     }
 
     fn size_hint(&self) -> Size<Length> {
         self.size_hint
     }
 
-    #[allow(unused_variables)]
-    fn layout(&mut self, viewport: Area) {}
+    fn layout(&mut self, _viewport: Area) {}
 }
 
 impl<'a, Message> From<Label> for Element<'a, Message> {
@@ -75,3 +95,44 @@ impl<'a, Message> From<Label> for Element<'a, Message> {
         Self::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backend::test_backend::TestBackend,
+        command::Command,
+        geometry::length::Length,
+        vertical,
+        widget::{
+            app::{App, Program},
+            element::Element,
+        },
+        widgets::vertical::Vertical,
+    };
+
+    use super::label;
+
+    struct Notes;
+
+    impl<'a> Program<'a, ()> for Notes {
+        fn update(&mut self, _message: ()) -> Command<()> {
+            Command::none()
+        }
+
+        fn view(&self) -> Element<'a, ()> {
+            vertical![label("one two three four").into(), label("end").into(),]
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        }
+    }
+
+    #[test]
+    fn wrap() {
+        // The height of the wrapped label is measured in the width it is given, so that
+        // the next label is below it from the first frame.
+        let mut app = App::with_backend(Notes, TestBackend::new(8, 5));
+        app.step().unwrap();
+        assert_eq!(app.backend().buffer().text(), "one two\nthree\nfour\nend\n");
+    }
+}
@@ -0,0 +1,409 @@
+use std::{
+    any::Any,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::{area::Area, length::Length, size::Size},
+    shell::Shell,
+    style::{color::Color, style::Style, theme::Theme},
+    text::{layout::TextLayout, Line, StyledText},
+    widget::{element::Element, widget::Widget},
+    widgets::scroll::{self, WHEEL_STEP},
+};
+
+/// The pause after which typing starts a new search.
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How many items of a [`List`] can be selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode {
+    Single,
+    Multiple,
+}
+
+/// The scroll, the highlighted item and the search of a [`List`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct State {
+    /// The index of the first visible item.
+    offset: usize,
+    /// The index of the highlighted item, moved with the keyboard. Starts on the first
+    /// selected item.
+    cursor: Option<usize>,
+    /// The prefix typed to search an item, and the time of the last typed character.
+    search: String,
+    searched_at: Option<Instant>,
+}
+
+/// A scrollable list of items, with a highlighted item moved with the keyboard and
+/// selected items.
+///
+/// Only the visible items are rendered, so a list can hold many items. Each item shows
+/// the first line of its text. Typing jumps to the next item starting with the typed
+/// text.
+pub struct List<Message> {
+    items: Vec<StyledText>,
+    selected: Vec<usize>,
+    mode: SelectionMode,
+    on_select: Option<Box<dyn Fn(usize) -> Message>>,
+    style: Style,
+    highlight_style: Style,
+    selected_style: Style,
+    background: Option<Color>,
+    bounds: Area,
+    state: State,
+    focused: bool,
+    size_hint: Size<Length>,
+}
+
+pub fn list<Message, T: Into<StyledText>>(items: impl IntoIterator<Item = T>) -> List<Message> {
+    List::new(items)
+}
+
+impl<Message> List<Message> {
+    pub fn new<T: Into<StyledText>>(items: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            items: items.into_iter().map(Into::into).collect(),
+            selected: Vec::new(),
+            mode: SelectionMode::Single,
+            on_select: None,
+            style: Style::new().foreground(Color::Text),
+            highlight_style: Style::new().background(Color::Selection),
+            selected_style: Style::new().foreground(Color::Primary).bold(),
+            background: None,
+            bounds: Area::zeros(),
+            state: State::default(),
+            focused: false,
+            size_hint: Size::new(Length::Fill, Length::Fill),
+        }
+    }
+
+    /// Set the indices of the selected items. Only the first one is used in the single
+    /// selection mode.
+    pub fn selected(mut self, selected: impl IntoIterator<Item = usize>) -> Self {
+        self.selected = selected.into_iter().collect();
+        if self.mode == SelectionMode::Single {
+            self.selected.truncate(1);
+        }
+        self
+    }
+
+    pub fn mode(mut self, mode: SelectionMode) -> Self {
+        self.mode = mode;
+        if mode == SelectionMode::Single {
+            self.selected.truncate(1);
+        }
+        self
+    }
+
+    /// Set the message produced with the index of an item chosen with Enter, Space or
+    /// a click. In the multiple selection mode, the program should toggle the item.
+    pub fn on_select(mut self, on_select: impl Fn(usize) -> Message + 'static) -> Self {
+        self.on_select = Some(Box::new(on_select));
+        self
+    }
+
+    /// Set the base style of the items.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style patched on the highlighted item.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Set the style patched on the selected items.
+    pub fn selected_style(mut self, style: Style) -> Self {
+        self.selected_style = style;
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.size_hint.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.size_hint.height = height.into();
+        self
+    }
+
+    /// Get the index of the highlighted item, if there are items.
+    fn cursor(&self) -> Option<usize> {
+        let last = self.items.len().checked_sub(1)?;
+        let cursor = self.state.cursor.or(self.selected.first().copied());
+        Some(cursor.unwrap_or(0).min(last))
+    }
+
+    fn scroll_to(&mut self, offset: usize) {
+        let visible = self.bounds.height as usize;
+        self.state.offset = scroll::clamp(offset, self.items.len(), visible);
+    }
+
+    /// Highlight an item and scroll to show it.
+    fn move_cursor(&mut self, index: usize) {
+        let visible = self.bounds.height as usize;
+        self.state.cursor = Some(index);
+        self.state.offset = scroll::reveal(self.state.offset, index, self.items.len(), visible);
+    }
+
+    /// Returns whether characters have been typed to search recently.
+    fn is_searching(&self) -> bool {
+        !self.state.search.is_empty()
+            && self
+                .state
+                .searched_at
+                .is_some_and(|time| time.elapsed() <= SEARCH_TIMEOUT)
+    }
+
+    /// Add a character to the search, and highlight the first item starting with the
+    /// searched text from the highlighted one.
+    fn search(&mut self, char: char, cursor: usize) {
+        if !self.is_searching() {
+            self.state.search.clear();
+        }
+        self.state.searched_at = Some(Instant::now());
+        self.state.search.extend(char.to_lowercase());
+
+        // A new search starts after the highlighted item, so that typing a letter again
+        // moves to the next item starting with it.
+        let start = if self.state.search.chars().count() == 1 {
+            cursor + 1
+        } else {
+            cursor
+        };
+        let count = self.items.len();
+        let found = (start..start + count).map(|i| i % count).find(|&i| {
+            self.items[i]
+                .lines
+                .first()
+                .is_some_and(|line| line.raw().to_lowercase().starts_with(&self.state.search))
+        });
+        if let Some(index) = found {
+            self.move_cursor(index);
+        }
+    }
+
+    fn select(&self, index: usize, shell: &mut Shell<Message>) {
+        if let Some(on_select) = &self.on_select {
+            shell.push(on_select(index));
+        }
+    }
+}
+
+impl<Message> Widget<Message> for List<Message> {
+    fn layout(&mut self, viewport: Area) {
+        self.bounds = viewport;
+        self.scroll_to(self.state.offset);
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() {
+            return;
+        }
+        match event {
+            Event::Key(event) if self.focused && event.kind != KeyEventKind::Release => {
+                let Some(cursor) = self.cursor() else {
+                    return;
+                };
+                let last = self.items.len() - 1;
+                let page = (self.bounds.height as usize).max(1);
+                match event.code {
+                    KeyCode::Up => self.move_cursor(cursor.saturating_sub(1)),
+                    KeyCode::Down => self.move_cursor((cursor + 1).min(last)),
+                    KeyCode::PageUp => self.move_cursor(cursor.saturating_sub(page)),
+                    KeyCode::PageDown => self.move_cursor((cursor + page).min(last)),
+                    KeyCode::Home => self.move_cursor(0),
+                    KeyCode::End => self.move_cursor(last),
+                    KeyCode::Enter => self.select(cursor, shell),
+                    // A space continues a search, and chooses the item otherwise.
+                    KeyCode::Char(' ') if !self.is_searching() => self.select(cursor, shell),
+                    KeyCode::Char(char)
+                        if !event
+                            .modifiers
+                            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+                    {
+                        self.search(char, cursor);
+                        shell.capture_event();
+                        return;
+                    }
+                    _ => return,
+                }
+                self.state.search.clear();
+                shell.capture_event();
+            }
+            Event::Mouse(event) if self.bounds.contains(event.column, event.row) => {
+                match event.kind {
+                    MouseEventKind::ScrollUp => {
+                        self.scroll_to(self.state.offset.saturating_sub(WHEEL_STEP))
+                    }
+                    MouseEventKind::ScrollDown => {
+                        self.scroll_to(self.state.offset.saturating_add(WHEEL_STEP))
+                    }
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        if !self.focused {
+                            self.focused = true;
+                            shell.request_focus();
+                        }
+                        let index = self.state.offset + (event.row - self.bounds.y) as usize;
+                        if index < self.items.len() {
+                            self.move_cursor(index);
+                            self.select(index, shell);
+                        }
+                    }
+                    _ => return,
+                }
+                shell.capture_event();
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, _theme: &Theme) {
+        buffer.render_background(area, self.background);
+        if area.is_empty() {
+            return;
+        }
+        let cursor = self.cursor();
+        let layout = TextLayout::new().wrap(false).truncate(true);
+        let items = self.items.iter().enumerate().skip(self.state.offset);
+        for (y, (index, item)) in (area.y..area.y + area.height).zip(items) {
+            let mut style = self.style;
+            if self.selected.contains(&index) {
+                style = style.patch(self.selected_style);
+            }
+            if cursor == Some(index) {
+                style = style.patch(self.highlight_style);
+                if self.focused {
+                    style = style.foreground(Color::Focus);
+                }
+            }
+            let row = Area::new(area.x, y, area.width, 1);
+            buffer.render_background(row, style.background);
+            let line = StyledText::new(item.lines.first().cloned());
+            buffer.render_text(&line, style, row, &layout);
+        }
+    }
+
+    fn size(&self) -> Size {
+        let width = self
+            .items
+            .iter()
+            .map(|item| item.lines.first().map_or(0, Line::width))
+            .max()
+            .unwrap_or(0);
+        let height = self.items.len().min(u16::MAX as usize) as u16;
+        Size::new(width, height)
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn take_state(&mut self) -> Option<Box<dyn Any>> {
+        Some(Box::new(std::mem::take(&mut self.state)))
+    }
+
+    fn restore(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<State>() {
+            self.state = *state;
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<List<Message>> for Element<'a, Message> {
+    fn from(value: List<Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::{
+        buffer::buffer::Buffer,
+        geometry::area::Area,
+        style::{color::Color, theme::Theme},
+        widget::widget::Widget,
+        widgets::testing::press,
+    };
+
+    use super::{list, List, SelectionMode};
+
+    const PLANETS: [&str; 8] = [
+        "Mercury", "Venus", "Earth", "Mars", "Jupiter", "Saturn", "Uranus", "Neptune",
+    ];
+
+    fn render(list: &List<usize>) -> Buffer {
+        let mut buffer = Buffer::new(8, 3);
+        list.render(Area::from_wh(8, 3), &mut buffer, &Theme::TOKYO_NIGHT);
+        buffer
+    }
+
+    #[test]
+    fn navigation() {
+        let mut list = list(PLANETS).on_select(|index| index);
+        list.layout(Area::from_wh(8, 3));
+        list.set_focused(true);
+
+        assert_eq!(press(&mut list, &[KeyCode::Down, KeyCode::Enter]), vec![1]);
+        assert_eq!(press(&mut list, &[KeyCode::End, KeyCode::Enter]), vec![7]);
+        assert_eq!(render(&list).text(), "Saturn\nUranus\nNeptune");
+        assert_eq!(
+            press(&mut list, &[KeyCode::PageUp, KeyCode::Enter]),
+            vec![4]
+        );
+        assert_eq!(press(&mut list, &[KeyCode::Home, KeyCode::Enter]), vec![0]);
+
+        // Type to search, and search again to cycle through the items.
+        let keys = [KeyCode::Char('m'), KeyCode::Char('a'), KeyCode::Enter];
+        assert_eq!(press(&mut list, &keys), vec![3]);
+        let keys = [KeyCode::Char('m'), KeyCode::Enter, KeyCode::Char(' ')];
+        assert_eq!(press(&mut list, &keys), vec![0, 0]);
+    }
+
+    #[test]
+    fn selection() {
+        let mut list = list(PLANETS)
+            .mode(SelectionMode::Multiple)
+            .selected([1, 2])
+            .on_select(|index| index);
+        list.layout(Area::from_wh(8, 3));
+        let buffer = render(&list);
+        assert_eq!(buffer.text(), "Mercury\nVenus\nEarth");
+        // The cursor starts on the first selected item.
+        assert_eq!(buffer.get(0, 0).style().background, None);
+        assert_eq!(buffer.get(0, 1).style().background, Some(Color::Selection));
+        assert_eq!(buffer.get(0, 2).style().foreground, Some(Color::Primary));
+
+        let list = list.mode(SelectionMode::Single);
+        assert_eq!(
+            render(&list).get(0, 2).style().foreground,
+            Some(Color::Text)
+        );
+    }
+}
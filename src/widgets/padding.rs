@@ -115,6 +115,9 @@ impl<'a, Message> Widget<Message> for Padding<'a, Message> {
     }
 
     fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() {
+            return;
+        }
         self.element.widget_mut().process_event(event, shell)
     }
 
@@ -125,4 +128,8 @@ impl<'a, Message> Widget<Message> for Padding<'a, Message> {
     fn size_hint(&self) -> Size<Length> {
         self.size_hint
     }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget<Message>> {
+        vec![self.element.widget_mut()]
+    }
 }
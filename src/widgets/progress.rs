@@ -0,0 +1,426 @@
+use std::{
+    any::Any,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    buffer::{buffer::Buffer, pixel::Pixel},
+    geometry::{area::Area, length::Length, size::Size},
+    style::{color::Color, rgb::RGB, style::Style, theme::Theme},
+    text::StyledText,
+    widget::{element::Element, widget::Widget},
+};
+
+/// The blocks filling one to eight eighths of a cell from the left.
+const EIGHTHS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// The time an indeterminate segment takes to move by an eighth of a cell.
+const STEP: Duration = Duration::from_millis(20);
+
+/// The clock of the animation of an indeterminate [`ProgressBar`] or [`Gauge`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct State {
+    /// When the indeterminate animation started.
+    started: Option<Instant>,
+}
+
+impl State {
+    fn elapsed(&self) -> Duration {
+        self.started
+            .map_or(Duration::ZERO, |started| started.elapsed())
+    }
+}
+
+/// The bar drawn by [`ProgressBar`] and [`Gauge`], with a precision of an eighth of a
+/// cell.
+#[derive(Debug, Clone, Copy)]
+struct Bar {
+    progress: f32,
+    indeterminate: bool,
+    filled: Color,
+    track: Option<Color>,
+}
+
+impl Bar {
+    fn new(progress: f32) -> Self {
+        Self {
+            progress: progress.clamp(0.0, 1.0),
+            indeterminate: false,
+            filled: Color::Primary,
+            track: Some(Color::Stripe),
+        }
+    }
+
+    /// Get the filled range of a row of `width` cells, in eighths of a cell.
+    fn range(&self, width: u16, elapsed: Duration) -> (u32, u32) {
+        let total = width as u32 * 8;
+        if !self.indeterminate {
+            return (0, (self.progress * total as f32).round() as u32);
+        }
+        // A segment of a quarter of the width bounces from one end to the other.
+        let length = (width as u32 / 4).max(1) * 8;
+        let travel = total.saturating_sub(length);
+        if travel == 0 {
+            return (0, total);
+        }
+        let step = (elapsed.as_millis() / STEP.as_millis() % (2 * travel as u128)) as u32;
+        let start = if step < travel {
+            step
+        } else {
+            2 * travel - step
+        };
+        (start, start + length)
+    }
+
+    fn render(&self, area: Area, elapsed: Duration, buffer: &mut Buffer) {
+        let (start, end) = self.range(area.width, elapsed);
+        let track = self.track.unwrap_or(Color::Background);
+        for (i, x) in (area.x..area.x + area.width).enumerate() {
+            let (left, right) = (i as u32 * 8, i as u32 * 8 + 8);
+            let covered = end.min(right).saturating_sub(start.max(left));
+            let (char, foreground, background) = match covered {
+                0 => (' ', self.filled, self.track),
+                8 => (' ', self.filled, Some(self.filled)),
+                // Round to the nearest cell without the blocks.
+                _ if buffer.is_ascii() && covered >= 4 => (' ', self.filled, Some(self.filled)),
+                _ if buffer.is_ascii() => (' ', self.filled, self.track),
+                _ if start <= left => (EIGHTHS[covered as usize - 1], self.filled, self.track),
+                // Filled from the right: draw the track on the left of a filled cell.
+                _ => (EIGHTHS[7 - covered as usize], track, Some(self.filled)),
+            };
+            let style = Style {
+                background,
+                ..Style::from_foreground(foreground)
+            };
+            for y in area.y..area.y + area.height {
+                buffer.render_pixel(x, y, Pixel::from_char(char).set_style(style));
+            }
+        }
+    }
+
+    /// Render a label centered on the bar. Each character takes the text or background
+    /// color of the theme, whichever contrasts the most with the bar below it.
+    fn render_label(&self, label: &StyledText, area: Area, buffer: &mut Buffer, theme: &Theme) {
+        let size = label.size();
+        let area = buffer.area().intersect(Area::new(
+            area.x + area.width.saturating_sub(size.width) / 2,
+            area.y + area.height.saturating_sub(size.height) / 2,
+            size.width.min(area.width),
+            size.height.min(area.height),
+        ));
+        // The backgrounds of the bar are kept below the label.
+        let backgrounds: Vec<RGB> = (area.y..area.y + area.height)
+            .flat_map(|y| (area.x..area.x + area.width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let background = buffer.get(x, y).style().background;
+                background.unwrap_or(Color::Background).on_theme(theme)
+            })
+            .collect();
+        buffer.render_spans(label, Style::new(), area, false);
+        let cells = (area.y..area.y + area.height)
+            .flat_map(|y| (area.x..area.x + area.width).map(move |x| (x, y)));
+        for ((x, y), background) in cells.zip(backgrounds) {
+            let foreground = if RGB::contrast(theme.text, background)
+                >= RGB::contrast(theme.background, background)
+            {
+                Color::Text
+            } else {
+                Color::Background
+            };
+            buffer.get_mut(x, y).style_mut().foreground = Some(foreground);
+        }
+    }
+}
+
+/// Get the label showing a progress as a rounded percentage.
+fn percentage(progress: f32) -> StyledText {
+    StyledText::from(format!("{:.0}%", progress * 100.0).as_str())
+}
+
+/// A one-row bar filled with the progress of a task, or with a segment bouncing from
+/// one end to the other while the progress is unknown.
+pub struct ProgressBar {
+    bar: Bar,
+    show_percentage: bool,
+    state: State,
+    size_hint: Size<Length>,
+}
+
+/// Create a progress bar filled up to `progress`, from 0 to 1.
+pub fn progress_bar(progress: f32) -> ProgressBar {
+    ProgressBar::new(progress)
+}
+
+impl ProgressBar {
+    pub fn new(progress: f32) -> Self {
+        Self {
+            bar: Bar::new(progress),
+            show_percentage: false,
+            state: State::default(),
+            size_hint: Size::new(Length::Fill, Length::Fixed(1)),
+        }
+    }
+
+    /// Animate a segment instead of showing the progress, for a task of unknown
+    /// length. The animation is driven by the frames drawn by the app.
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.bar.indeterminate = indeterminate;
+        self
+    }
+
+    /// Show the progress as a percentage centered on the bar.
+    pub fn show_percentage(mut self, show_percentage: bool) -> Self {
+        self.show_percentage = show_percentage;
+        self
+    }
+
+    /// Set the color of the filled part of the bar.
+    pub fn filled(mut self, color: impl Into<Color>) -> Self {
+        self.bar.filled = color.into();
+        self
+    }
+
+    /// Set the color of the empty part of the bar.
+    pub fn track(mut self, color: Option<Color>) -> Self {
+        self.bar.track = color;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.size_hint.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.size_hint.height = height.into();
+        self
+    }
+}
+
+impl<Message> Widget<Message> for ProgressBar {
+    fn layout(&mut self, _viewport: Area) {
+        if self.bar.indeterminate && self.state.started.is_none() {
+            self.state.started = Some(Instant::now());
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, theme: &Theme) {
+        if area.is_empty() {
+            return;
+        }
+        self.bar.render(area, self.state.elapsed(), buffer);
+        if self.show_percentage && !self.bar.indeterminate {
+            self.bar
+                .render_label(&percentage(self.bar.progress), area, buffer, theme);
+        }
+    }
+
+    fn size(&self) -> Size {
+        Size::new(percentage(1.0).size().width, 1)
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn take_state(&mut self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.state))
+    }
+
+    fn restore(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<State>() {
+            self.state = *state;
+        }
+    }
+}
+
+impl<'a, Message> From<ProgressBar> for Element<'a, Message> {
+    fn from(value: ProgressBar) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A block filling its whole area with the progress of a task, with a label centered
+/// on it, the percentage by default.
+pub struct Gauge {
+    bar: Bar,
+    label: Option<StyledText>,
+    state: State,
+    size_hint: Size<Length>,
+}
+
+/// Create a gauge filled up to `progress`, from 0 to 1.
+pub fn gauge(progress: f32) -> Gauge {
+    Gauge::new(progress)
+}
+
+impl Gauge {
+    pub fn new(progress: f32) -> Self {
+        Self {
+            bar: Bar::new(progress),
+            label: None,
+            state: State::default(),
+            size_hint: Size::new(Length::Fill, Length::Fill),
+        }
+    }
+
+    /// Animate a segment instead of showing the progress, for a task of unknown
+    /// length. The animation is driven by the frames drawn by the app.
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.bar.indeterminate = indeterminate;
+        self
+    }
+
+    /// Set the label shown instead of the percentage.
+    pub fn label(mut self, label: impl Into<StyledText>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the color of the filled part of the gauge.
+    pub fn filled(mut self, color: impl Into<Color>) -> Self {
+        self.bar.filled = color.into();
+        self
+    }
+
+    /// Set the color of the empty part of the gauge.
+    pub fn track(mut self, color: Option<Color>) -> Self {
+        self.bar.track = color;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.size_hint.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.size_hint.height = height.into();
+        self
+    }
+
+    /// Get the text of the label. An indeterminate gauge has no percentage.
+    fn text(&self) -> Option<StyledText> {
+        match (&self.label, self.bar.indeterminate) {
+            (Some(label), _) => Some(label.clone()),
+            (None, false) => Some(percentage(self.bar.progress)),
+            (None, true) => None,
+        }
+    }
+}
+
+impl<Message> Widget<Message> for Gauge {
+    fn layout(&mut self, _viewport: Area) {
+        if self.bar.indeterminate && self.state.started.is_none() {
+            self.state.started = Some(Instant::now());
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, theme: &Theme) {
+        if area.is_empty() {
+            return;
+        }
+        self.bar.render(area, self.state.elapsed(), buffer);
+        if let Some(label) = self.text() {
+            self.bar.render_label(&label, area, buffer, theme);
+        }
+    }
+
+    fn size(&self) -> Size {
+        let label = self.text().map_or(Size::new(0, 1), |label| label.size());
+        Size::new(label.width + 2, label.height.max(1))
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn take_state(&mut self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.state))
+    }
+
+    fn restore(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<State>() {
+            self.state = *state;
+        }
+    }
+}
+
+impl<'a, Message> From<Gauge> for Element<'a, Message> {
+    fn from(value: Gauge) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::{
+        buffer::buffer::Buffer,
+        geometry::area::Area,
+        style::{
+            color::{Color, ColorSystem},
+            theme::Theme,
+        },
+        widget::widget::Widget,
+    };
+
+    use super::{gauge, progress_bar, STEP};
+
+    fn foreground(buffer: &Buffer, x: u16) -> Option<Color> {
+        buffer.get(x, 0).style().foreground
+    }
+
+    fn background(buffer: &Buffer, x: u16) -> Option<Color> {
+        buffer.get(x, 0).style().background
+    }
+
+    #[test]
+    fn progress() {
+        let theme = Theme::TOKYO_NIGHT;
+        let mut buffer = Buffer::new(4, 1);
+        let bar = progress_bar(0.45);
+        Widget::<()>::render(&bar, Area::from_wh(4, 1), &mut buffer, &theme);
+        assert_eq!(buffer.text(), " ▊");
+        assert_eq!(background(&buffer, 0), Some(Color::Primary));
+        assert_eq!(foreground(&buffer, 1), Some(Color::Primary));
+        assert_eq!(background(&buffer, 1), Some(Color::Stripe));
+
+        // The label contrasts with the filled part and the track.
+        let mut buffer = Buffer::new(10, 1);
+        let bar = progress_bar(0.5).show_percentage(true);
+        Widget::<()>::render(&bar, Area::from_wh(10, 1), &mut buffer, &theme);
+        assert_eq!(buffer.text(), "   50%");
+        assert_eq!(foreground(&buffer, 4), Some(Color::Background));
+        assert_eq!(background(&buffer, 4), Some(Color::Primary));
+        assert_eq!(foreground(&buffer, 5), Some(Color::Text));
+        assert_eq!(background(&buffer, 5), Some(Color::Stripe));
+    }
+
+    #[test]
+    fn indeterminate() {
+        let area = Area::from_wh(8, 1);
+        let mut gauge = gauge(0.0).indeterminate(true);
+        Widget::<()>::layout(&mut gauge, area);
+        // The segment of two cells has moved by a cell and a half.
+        let mut buffer = Buffer::new(8, 1);
+        gauge.bar.render(area, STEP * 12, &mut buffer);
+        assert_eq!(buffer.text(), " ▌ ▌");
+        assert_eq!(background(&buffer, 1), Some(Color::Primary));
+        assert_eq!(background(&buffer, 2), Some(Color::Primary));
+        assert_eq!(background(&buffer, 3), Some(Color::Stripe));
+        buffer.set_color_system(ColorSystem::LegacyWindows);
+        gauge.bar.render(area, STEP * 12, &mut buffer);
+        assert_eq!(buffer.text(), "");
+        assert_eq!(background(&buffer, 3), Some(Color::Primary));
+
+        // The state keeps the animation going across frames.
+        gauge.state.started = Some(Instant::now() - Duration::from_secs(1));
+        let state = Widget::<()>::take_state(&mut gauge).unwrap();
+        let mut next = super::gauge(0.0).indeterminate(true);
+        Widget::<()>::restore(&mut next, state);
+        Widget::<()>::layout(&mut next, area);
+        assert!(next.state.elapsed() >= Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,432 @@
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind};
+
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::{area::Area, length::Length, size::Size},
+    shell::Shell,
+    style::{color::Color, style::Style, theme::Theme},
+    text::{Line, StyledText},
+    widget::{element::Element, widget::Widget},
+};
+
+/// The number of columns of the button and the space before the label.
+const BUTTON_WIDTH: u16 = 4;
+
+/// Render a radio button followed by its label in the first row of an area.
+fn render_option(
+    label: &StyledText,
+    selected: bool,
+    style: Style,
+    area: Area,
+    buffer: &mut Buffer,
+) {
+    let glyph = match (selected, buffer.is_ascii()) {
+        (true, false) => "(●)",
+        (true, true) => "(*)",
+        (false, _) => "( )",
+    };
+    let glyph_style = Style::new().foreground(if selected {
+        Color::Primary
+    } else {
+        Color::Text
+    });
+    buffer.render_string(glyph, glyph_style, area, false);
+    let line = StyledText::new(label.lines.first().cloned());
+    let label = Area::new(
+        area.x.saturating_add(BUTTON_WIDTH),
+        area.y,
+        area.width.saturating_sub(BUTTON_WIDTH),
+        area.height.min(1),
+    );
+    buffer.render_spans(&line, style, label, false);
+}
+
+/// A radio button followed by a label, one of the choices of a program.
+/// See [`RadioGroup`] to lay out the choices of a value together.
+pub struct Radio<Message: Clone> {
+    label: StyledText,
+    selected: bool,
+    on_select: Option<Message>,
+    style: Style,
+    background: Option<Color>,
+    bounds: Area,
+    focused: bool,
+    size_hint: Size<Length>,
+}
+
+pub fn radio<Message: Clone>(label: impl Into<StyledText>, selected: bool) -> Radio<Message> {
+    Radio::new(label, selected)
+}
+
+impl<Message: Clone> Radio<Message> {
+    pub fn new(label: impl Into<StyledText>, selected: bool) -> Self {
+        Self {
+            label: label.into(),
+            selected,
+            on_select: None,
+            style: Style::new().foreground(Color::Text),
+            background: None,
+            bounds: Area::zeros(),
+            focused: false,
+            size_hint: Size::preferred(),
+        }
+    }
+
+    /// Set the message produced when the user selects the unselected radio button with
+    /// Space or a click.
+    pub fn on_select(mut self, message: Message) -> Self {
+        self.on_select = Some(message);
+        self
+    }
+
+    /// Set the base style of the label.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.size_hint.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.size_hint.height = height.into();
+        self
+    }
+}
+
+impl<Message: Clone> Widget<Message> for Radio<Message> {
+    fn layout(&mut self, viewport: Area) {
+        self.bounds = viewport;
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() {
+            return;
+        }
+        let selected = match event {
+            Event::Key(event) => {
+                self.focused
+                    && event.kind == KeyEventKind::Press
+                    && event.code == KeyCode::Char(' ')
+            }
+            Event::Mouse(event) => {
+                event.kind == MouseEventKind::Down(MouseButton::Left)
+                    && self.bounds.contains(event.column, event.row)
+            }
+            _ => false,
+        };
+        if !selected {
+            return;
+        }
+        shell.capture_event();
+        if !self.focused {
+            self.focused = true;
+            shell.request_focus();
+        }
+        if let Some(message) = self.on_select.clone().filter(|_| !self.selected) {
+            shell.push(message);
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, _theme: &Theme) {
+        buffer.render_background(area, self.background);
+        let mut style = self.style;
+        if self.focused {
+            style = style.foreground(Color::Focus);
+        }
+        render_option(&self.label, self.selected, style, area, buffer);
+    }
+
+    fn size(&self) -> Size {
+        let label = self.label.lines.first().map_or(0, Line::width);
+        Size::new(BUTTON_WIDTH + label, 1)
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+}
+
+impl<'a, Message: Clone + 'a> From<Radio<Message>> for Element<'a, Message> {
+    fn from(value: Radio<Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Radio buttons of the choices of a value, one per row, focused as a whole.
+/// The arrow keys move the selection, and a click selects a choice.
+pub struct RadioGroup<Value, Message> {
+    options: Vec<(StyledText, Value)>,
+    selected: Option<Value>,
+    on_select: Option<Box<dyn Fn(Value) -> Message>>,
+    style: Style,
+    background: Option<Color>,
+    bounds: Area,
+    focused: bool,
+    size_hint: Size<Length>,
+}
+
+pub fn radio_group<Value: Clone + PartialEq, Message, T: Into<StyledText>>(
+    options: impl IntoIterator<Item = (T, Value)>,
+    selected: Option<Value>,
+) -> RadioGroup<Value, Message> {
+    RadioGroup::new(options, selected)
+}
+
+impl<Value: Clone + PartialEq, Message> RadioGroup<Value, Message> {
+    pub fn new<T: Into<StyledText>>(
+        options: impl IntoIterator<Item = (T, Value)>,
+        selected: Option<Value>,
+    ) -> Self {
+        Self {
+            options: options
+                .into_iter()
+                .map(|(label, value)| (label.into(), value))
+                .collect(),
+            selected,
+            on_select: None,
+            style: Style::new().foreground(Color::Text),
+            background: None,
+            bounds: Area::zeros(),
+            focused: false,
+            size_hint: Size::preferred(),
+        }
+    }
+
+    /// Set the message produced with the value of the choice selected by the user.
+    pub fn on_select(mut self, on_select: impl Fn(Value) -> Message + 'static) -> Self {
+        self.on_select = Some(Box::new(on_select));
+        self
+    }
+
+    /// Set the base style of the labels.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.size_hint.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.size_hint.height = height.into();
+        self
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        let selected = self.selected.as_ref()?;
+        self.options.iter().position(|(_, value)| value == selected)
+    }
+
+    fn select(&self, index: usize, shell: &mut Shell<Message>) {
+        if let (Some(on_select), Some((_, value))) = (&self.on_select, self.options.get(index)) {
+            if self.selected.as_ref() != Some(value) {
+                shell.push(on_select(value.clone()));
+            }
+        }
+    }
+}
+
+impl<Value: Clone + PartialEq, Message> Widget<Message> for RadioGroup<Value, Message> {
+    fn layout(&mut self, viewport: Area) {
+        self.bounds = viewport;
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() || self.options.is_empty() {
+            return;
+        }
+        let last = self.options.len() - 1;
+        match event {
+            Event::Key(event) if self.focused && event.kind != KeyEventKind::Release => {
+                let index = match (event.code, self.selected_index()) {
+                    (KeyCode::Up | KeyCode::Left, Some(i)) => i.saturating_sub(1),
+                    (KeyCode::Down | KeyCode::Right, Some(i)) => (i + 1).min(last),
+                    (KeyCode::Char(' '), Some(i)) => i,
+                    (KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right, None)
+                    | (KeyCode::Char(' '), None) => 0,
+                    _ => return,
+                };
+                shell.capture_event();
+                self.select(index, shell);
+            }
+            Event::Mouse(event)
+                if event.kind == MouseEventKind::Down(MouseButton::Left)
+                    && self.bounds.contains(event.column, event.row) =>
+            {
+                shell.capture_event();
+                if !self.focused {
+                    self.focused = true;
+                    shell.request_focus();
+                }
+                let index = (event.row - self.bounds.y) as usize;
+                if index <= last {
+                    self.select(index, shell);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, _theme: &Theme) {
+        buffer.render_background(area, self.background);
+        // The focus is shown on the selected choice, or the first one.
+        let focused = self.focused.then(|| self.selected_index().unwrap_or(0));
+        let rows = area.y..area.y.saturating_add(area.height);
+        for (i, (y, (label, value))) in rows.zip(&self.options).enumerate() {
+            let mut style = self.style;
+            if focused == Some(i) {
+                style = style.foreground(Color::Focus);
+            }
+            let selected = self.selected.as_ref() == Some(value);
+            let row = Area::new(area.x, y, area.width, 1);
+            render_option(label, selected, style, row, buffer);
+        }
+    }
+
+    fn size(&self) -> Size {
+        let width = self
+            .options
+            .iter()
+            .map(|(label, _)| label.lines.first().map_or(0, Line::width))
+            .max()
+            .unwrap_or(0);
+        let height = self.options.len().min(u16::MAX as usize) as u16;
+        Size::new(BUTTON_WIDTH + width, height)
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+}
+
+impl<'a, Value: Clone + PartialEq + 'a, Message: 'a> From<RadioGroup<Value, Message>>
+    for Element<'a, Message>
+{
+    fn from(value: RadioGroup<Value, Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+
+    use crate::{
+        buffer::buffer::Buffer,
+        geometry::area::Area,
+        shell::Shell,
+        style::{color::ColorSystem, theme::Theme},
+        widget::widget::Widget,
+    };
+
+    use super::{radio, radio_group, RadioGroup};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Size {
+        Small,
+        Medium,
+        Large,
+    }
+
+    fn sizes(selected: Option<Size>) -> RadioGroup<Size, Size> {
+        radio_group(
+            [
+                ("Small", Size::Small),
+                ("Medium", Size::Medium),
+                ("Large", Size::Large),
+            ],
+            selected,
+        )
+        .on_select(|size| size)
+    }
+
+    #[test]
+    fn group() {
+        let mut group = sizes(Some(Size::Medium));
+        let area = Area::from_wh(10, 3);
+        group.layout(area);
+        let mut buffer = Buffer::new(10, 3);
+        group.render(area, &mut buffer, &Theme::TOKYO_NIGHT);
+        assert_eq!(buffer.text(), "( ) Small\n(●) Medium\n( ) Large");
+        buffer.set_color_system(ColorSystem::LegacyWindows);
+        group.render(area, &mut buffer, &Theme::TOKYO_NIGHT);
+        assert_eq!(buffer.text(), "( ) Small\n(*) Medium\n( ) Large");
+
+        group.set_focused(true);
+        let mut shell = Shell::new();
+        group.process_event(
+            Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)),
+            &mut shell,
+        );
+        assert_eq!(shell.messages(), &vec![Size::Large]);
+
+        let mut shell = Shell::new();
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 2,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+        group.process_event(Event::Mouse(click), &mut shell);
+        assert_eq!(shell.messages(), &vec![Size::Small]);
+    }
+
+    #[test]
+    fn single() {
+        let mut radio = radio("Large", true).on_select(Size::Large);
+        radio.set_focused(true);
+        let space = Event::Key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        let mut shell = Shell::new();
+        radio.process_event(space.clone(), &mut shell);
+        // A selected radio button is not selected again.
+        assert!(shell.messages().is_empty());
+        assert!(shell.is_event_captured());
+
+        let mut radio = super::radio("Small", false).on_select(Size::Small);
+        radio.set_focused(true);
+        let mut shell = Shell::new();
+        radio.process_event(space, &mut shell);
+        assert_eq!(shell.messages(), &vec![Size::Small]);
+    }
+}
@@ -0,0 +1,38 @@
+/// The number of rows scrolled by a mouse wheel step.
+pub(crate) const WHEEL_STEP: usize = 3;
+
+/// Get the offset of the first visible row, not further than showing the last row at
+/// the bottom of `visible` rows.
+pub(crate) fn clamp(offset: usize, count: usize, visible: usize) -> usize {
+    offset.min(count.saturating_sub(visible))
+}
+
+/// Get the offset of the first visible row after scrolling as little as needed to show
+/// `row`.
+pub(crate) fn reveal(offset: usize, row: usize, count: usize, visible: usize) -> usize {
+    let shown = visible.max(1);
+    let offset = if row < offset {
+        row
+    } else if row >= offset + shown {
+        row + 1 - shown
+    } else {
+        offset
+    };
+    clamp(offset, count, visible)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clamp, reveal};
+
+    #[test]
+    fn scroll() {
+        assert_eq!(clamp(8, 10, 4), 6);
+        assert_eq!(clamp(8, 3, 4), 0);
+        assert_eq!(reveal(0, 5, 10, 4), 2);
+        assert_eq!(reveal(4, 1, 10, 4), 1);
+        assert_eq!(reveal(2, 3, 10, 4), 2);
+        // Without room, the row is still scrolled to.
+        assert_eq!(reveal(0, 5, 10, 0), 5);
+    }
+}
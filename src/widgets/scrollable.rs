@@ -0,0 +1,389 @@
+use std::any::Any;
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseEvent, MouseEventKind};
+
+use crate::{
+    buffer::{buffer::Buffer, pixel::Pixel},
+    geometry::{area::Area, length::Length, size::Size},
+    shell::Shell,
+    style::{color::Color, style::Style, theme::Theme},
+    widget::{element::Element, widget::Widget},
+    widgets::scroll::WHEEL_STEP,
+};
+
+/// The scroll position of a [`Scrollable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct State {
+    /// The number of rows scrolled out on the top.
+    offset: u16,
+    /// Whether the content is scrolled to the bottom.
+    at_bottom: bool,
+}
+
+pub struct Scrollable<'a, Message> {
+    element: Element<'a, Message>,
+    /// The bounds of the content, relative to the content itself.
+    content_bounds: Area,
+    bounds: Area,
+    background: Option<Color>,
+    stick_to_bottom: bool,
+    state: State,
+    focused: bool,
+    size_hint: Size<Length>,
+}
+
+pub fn scrollable<'a, Message>(
+    element: impl Into<Element<'a, Message>>,
+) -> Scrollable<'a, Message> {
+    Scrollable::new(element)
+}
+
+/// Returns whether the widget or one of its descendants has the keyboard focus.
+fn contains_focus<Message>(widget: &mut dyn Widget<Message>) -> bool {
+    widget.is_focused()
+        || widget
+            .children_mut()
+            .into_iter()
+            .any(|child| contains_focus(child))
+}
+
+impl<'a, Message> Scrollable<'a, Message> {
+    pub fn new(element: impl Into<Element<'a, Message>>) -> Self {
+        Self {
+            element: element.into(),
+            content_bounds: Area::zeros(),
+            bounds: Area::zeros(),
+            background: None,
+            stick_to_bottom: false,
+            state: State {
+                offset: 0,
+                at_bottom: false,
+            },
+            focused: false,
+            size_hint: Size::new(Length::Fill, Length::Fill),
+        }
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Keep the content scrolled to the bottom while it grows, unless the user scrolls
+    /// up. Useful for logs.
+    pub fn stick_to_bottom(mut self, stick_to_bottom: bool) -> Self {
+        self.stick_to_bottom = stick_to_bottom;
+        self.state.at_bottom = stick_to_bottom;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.size_hint.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.size_hint.height = height.into();
+        self
+    }
+
+    fn max_offset(&self) -> u16 {
+        self.content_bounds
+            .height
+            .saturating_sub(self.bounds.height)
+    }
+
+    fn has_scrollbar(&self) -> bool {
+        self.content_bounds.height > self.bounds.height
+    }
+
+    /// The area of the screen showing the content, i.e. the bounds without the scrollbar.
+    fn viewport(&self) -> Area {
+        let scrollbar = self.has_scrollbar() as u16;
+        Area::new(
+            self.bounds.x,
+            self.bounds.y,
+            self.bounds.width.saturating_sub(scrollbar),
+            self.bounds.height,
+        )
+    }
+
+    fn scroll_to(&mut self, offset: u16) {
+        self.state.offset = offset.min(self.max_offset());
+        self.state.at_bottom = self.state.offset == self.max_offset();
+    }
+}
+
+impl<Message> Widget<Message> for Scrollable<'_, Message> {
+    fn layout(&mut self, viewport: Area) {
+        self.bounds = viewport;
+        let widget = self.element.widget();
+        let preferred = widget.size();
+        let overflow = preferred.height > viewport.height;
+        let max_width = viewport.width.saturating_sub(overflow as u16);
+        let width = match widget.size_hint().width {
+            Length::Preferred => preferred.width.min(max_width),
+            Length::Fixed(width) => width.min(max_width),
+            _ => max_width,
+        };
+        let height = match widget.size_hint().height {
+            Length::Fixed(height) => height,
+            _ => preferred.height.max(viewport.height),
+        };
+        self.content_bounds = Area::new(0, 0, width, height);
+        self.element.widget_mut().layout(self.content_bounds);
+
+        if self.stick_to_bottom && self.state.at_bottom {
+            self.state.offset = self.max_offset();
+        }
+        self.scroll_to(self.state.offset);
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() {
+            return;
+        }
+        match event {
+            Event::Mouse(event) => {
+                // The content is laid out in its own coordinates, translate the position.
+                // Dragging may go beyond the viewport, but a press must be inside it.
+                // A position above or left of the content has no coordinates, so the
+                // event is dropped.
+                let inside = self.viewport().contains(event.column, event.row);
+                if inside || matches!(event.kind, MouseEventKind::Drag(_) | MouseEventKind::Up(_)) {
+                    let column = event.column.checked_sub(self.bounds.x);
+                    let row = event.row as i32 - self.bounds.y as i32 + self.state.offset as i32;
+                    if let (Some(column), Ok(row)) = (column, u16::try_from(row)) {
+                        let translated = MouseEvent {
+                            column,
+                            row,
+                            ..event
+                        };
+                        self.element
+                            .widget_mut()
+                            .process_event(Event::Mouse(translated), shell);
+                    }
+                }
+                if shell.is_event_captured() || !self.bounds.contains(event.column, event.row) {
+                    return;
+                }
+                match event.kind {
+                    MouseEventKind::ScrollUp => {
+                        self.scroll_to(self.state.offset.saturating_sub(WHEEL_STEP as u16))
+                    }
+                    MouseEventKind::ScrollDown => {
+                        self.scroll_to(self.state.offset.saturating_add(WHEEL_STEP as u16))
+                    }
+                    _ => return,
+                }
+                shell.capture_event();
+            }
+            Event::Key(event) => {
+                self.element
+                    .widget_mut()
+                    .process_event(Event::Key(event), shell);
+                if shell.is_event_captured()
+                    || event.kind == KeyEventKind::Release
+                    || !contains_focus(self)
+                {
+                    return;
+                }
+                let page = self.bounds.height.max(1);
+                let offset = self.state.offset;
+                match event.code {
+                    KeyCode::PageUp => self.scroll_to(offset.saturating_sub(page)),
+                    KeyCode::PageDown => self.scroll_to(offset.saturating_add(page)),
+                    KeyCode::Up if self.focused => self.scroll_to(offset.saturating_sub(1)),
+                    KeyCode::Down if self.focused => self.scroll_to(offset.saturating_add(1)),
+                    KeyCode::Home if self.focused => self.scroll_to(0),
+                    KeyCode::End if self.focused => self.scroll_to(u16::MAX),
+                    _ => return,
+                }
+                shell.capture_event();
+            }
+            event => self.element.widget_mut().process_event(event, shell),
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, theme: &Theme) {
+        buffer.render_background(area, self.background);
+        // Only the visible rows of the content are drawn, the rest is dropped.
+        let window = Area::new(0, self.state.offset, self.viewport().width, area.height);
+        let mut content = Buffer::window(self.content_bounds.intersect(window));
+        content.set_color_system(buffer.color_system());
+        self.element
+            .widget()
+            .render(self.content_bounds, &mut content, theme);
+        buffer.render(area.x, area.y, &content);
+
+        if !self.has_scrollbar() || area.is_empty() {
+            return;
+        }
+        let x = area.x + area.width - 1;
+        let height = area.height;
+        let thumb_height =
+            (height as u32 * height as u32 / self.content_bounds.height as u32).max(1) as u16;
+        let thumb_y = (self.state.offset as u32 * (height - thumb_height) as u32
+            / self.max_offset().max(1) as u32) as u16;
+        let track = Style::new().foreground(theme.background.brighter(0.2));
+        let thumb = Style::new().foreground(if self.focused {
+            Color::Focus
+        } else {
+            Color::Primary
+        });
+        for y in 0..height {
+            let (char, style) = if (thumb_y..thumb_y + thumb_height).contains(&y) {
+                ('┃', thumb)
+            } else {
+                ('│', track)
+            };
+            buffer.render_pixel(x, area.y + y, Pixel::from_char(char).set_style(style));
+        }
+    }
+
+    fn size(&self) -> Size {
+        self.element.widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    /// Only a scrollable with a scrollbar is focusable, to scroll it with the keyboard.
+    fn is_focusable(&self) -> bool {
+        self.has_scrollbar()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn children_origin(&self) -> (i32, i32) {
+        (
+            self.bounds.x as i32,
+            self.bounds.y as i32 - self.state.offset as i32,
+        )
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget<Message>> {
+        vec![self.element.widget_mut()]
+    }
+
+    fn take_state(&mut self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.state))
+    }
+
+    fn restore(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<State>() {
+            self.state = *state;
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<Scrollable<'a, Message>> for Element<'a, Message> {
+    fn from(value: Scrollable<'a, Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    use crate::{
+        buffer::buffer::Buffer,
+        geometry::area::Area,
+        shell::Shell,
+        style::theme::Theme,
+        widget::{element::Element, widget::Widget},
+        widgets::{button::button, checkbox::checkbox, label::label, vertical::Vertical},
+    };
+
+    use super::{scrollable, Scrollable};
+
+    fn render(scrollable: &mut Scrollable<usize>, width: u16, height: u16) -> String {
+        let area = Area::from_wh(width, height);
+        scrollable.layout(area);
+        let mut buffer = Buffer::new(width, height);
+        scrollable.render(area, &mut buffer, &Theme::TOKYO_NIGHT);
+        buffer.text()
+    }
+
+    fn mouse(kind: MouseEventKind, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column: 1,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn scroll() {
+        let lines: Vec<Element<usize>> = ["A\nB", "C", "D", "E", "F"]
+            .into_iter()
+            .map(|text| label(text).into())
+            .collect();
+        let mut scrollable = scrollable(Vertical::with_children(lines));
+        assert_eq!(render(&mut scrollable, 4, 3), "A  ┃\nB  │\nC  │");
+        assert!(scrollable.is_focusable());
+
+        // The wheel scrolls by steps, up to the bottom, and the first label is cut.
+        let mut shell = Shell::new();
+        scrollable.process_event(mouse(MouseEventKind::ScrollDown, 1), &mut shell);
+        assert!(shell.is_event_captured());
+        assert_eq!(render(&mut scrollable, 4, 3), "D  │\nE  │\nF  ┃");
+        scrollable.process_event(mouse(MouseEventKind::ScrollDown, 1), &mut Shell::new());
+        assert_eq!(render(&mut scrollable, 4, 3), "D  │\nE  │\nF  ┃");
+        scrollable.process_event(mouse(MouseEventKind::ScrollUp, 1), &mut Shell::new());
+        assert_eq!(render(&mut scrollable, 4, 3), "A  ┃\nB  │\nC  │");
+        scrollable.process_event(mouse(MouseEventKind::ScrollDown, 1), &mut Shell::new());
+
+        // The offset is clamped when the viewport grows.
+        assert_eq!(
+            render(&mut scrollable, 4, 5),
+            "B  │\nC  ┃\nD  ┃\nE  ┃\nF  ┃"
+        );
+        assert_eq!(render(&mut scrollable, 4, 6), "A\nB\nC\nD\nE\nF");
+        // Without a scrollbar, there is nothing to scroll with the keyboard.
+        assert!(!scrollable.is_focusable());
+    }
+
+    #[test]
+    fn translate() {
+        let boxes: Vec<Element<usize>> = (0..6)
+            .map(|i| {
+                checkbox(format!("{}", i), false)
+                    .on_toggle(move |_| i)
+                    .into()
+            })
+            .collect();
+        let mut scrollable = scrollable(Vertical::with_children(boxes));
+        render(&mut scrollable, 8, 2);
+        scrollable.process_event(mouse(MouseEventKind::ScrollDown, 0), &mut Shell::new());
+        render(&mut scrollable, 8, 2);
+
+        // The click on the second row reaches the second visible checkbox.
+        let mut shell = Shell::new();
+        scrollable.process_event(
+            mouse(MouseEventKind::Down(MouseButton::Left), 1),
+            &mut shell,
+        );
+        assert_eq!(shell.messages(), &vec![4]);
+
+        // A release above the content is dropped instead of hitting its first row.
+        let mut buttons = Scrollable::new(Vertical::with_children(vec![button("Ok")
+            .on_click(1)
+            .into()]));
+        buttons.layout(Area::new(0, 3, 8, 2));
+        let left = MouseButton::Left;
+        buttons.process_event(mouse(MouseEventKind::Down(left), 3), &mut Shell::new());
+        let mut shell = Shell::new();
+        buttons.process_event(mouse(MouseEventKind::Up(left), 0), &mut shell);
+        assert!(shell.messages().is_empty());
+    }
+}
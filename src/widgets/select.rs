@@ -0,0 +1,432 @@
+use std::any::Any;
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::{area::Area, length::Length, size::Size},
+    shell::Shell,
+    style::{color::Color, style::Style, theme::Theme},
+    text::{layout::TextLayout, Line, StyledText},
+    widget::{element::Element, widget::Widget},
+    widgets::scroll::{self, WHEEL_STEP},
+};
+
+/// The maximum number of options shown at once by the popup.
+const MAX_ROWS: usize = 8;
+
+/// The popup of a [`Select`]: whether it is open, its filter and its scroll.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct State {
+    open: bool,
+    /// The text typed to filter the options while the popup is open.
+    filter: String,
+    /// The index of the highlighted option, and of the first visible one, among the
+    /// options matching the filter.
+    cursor: usize,
+    offset: usize,
+}
+
+/// A dropdown showing the selected option, which opens a popup list of the options
+/// when activated.
+///
+/// The popup is drawn above the other widgets, below the dropdown or above it if there
+/// is no room. Typing while it is open filters the options containing the typed text.
+pub struct Select<Message> {
+    options: Vec<StyledText>,
+    selected: Option<usize>,
+    placeholder: StyledText,
+    on_select: Option<Box<dyn Fn(usize) -> Message>>,
+    style: Style,
+    highlight_style: Style,
+    selected_style: Style,
+    background: Option<Color>,
+    popup_background: Option<Color>,
+    bounds: Area,
+    /// The area of the popup, laid out by [`Widget::overlay`].
+    popup: Area,
+    state: State,
+    focused: bool,
+    size_hint: Size<Length>,
+}
+
+pub fn select<Message, T: Into<StyledText>>(
+    options: impl IntoIterator<Item = T>,
+    selected: Option<usize>,
+) -> Select<Message> {
+    Select::new(options, selected)
+}
+
+impl<Message> Select<Message> {
+    pub fn new<T: Into<StyledText>>(
+        options: impl IntoIterator<Item = T>,
+        selected: Option<usize>,
+    ) -> Self {
+        Self {
+            options: options.into_iter().map(Into::into).collect(),
+            selected,
+            placeholder: StyledText::from(""),
+            on_select: None,
+            style: Style::new().foreground(Color::Text),
+            highlight_style: Style::new().background(Color::Selection),
+            selected_style: Style::new().foreground(Color::Primary).bold(),
+            background: None,
+            popup_background: Some(Color::Stripe),
+            bounds: Area::zeros(),
+            popup: Area::zeros(),
+            state: State::default(),
+            focused: false,
+            size_hint: Size::preferred(),
+        }
+    }
+
+    /// Set the text shown when no option is selected.
+    pub fn placeholder(mut self, placeholder: impl Into<StyledText>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Set the message produced with the index of an option chosen in the popup with
+    /// Enter or a click.
+    pub fn on_select(mut self, on_select: impl Fn(usize) -> Message + 'static) -> Self {
+        self.on_select = Some(Box::new(on_select));
+        self
+    }
+
+    /// Set the base style of the options.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style patched on the highlighted option of the popup.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Set the style patched on the selected option of the popup.
+    pub fn selected_style(mut self, style: Style) -> Self {
+        self.selected_style = style;
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn popup_background(mut self, background: Option<Color>) -> Self {
+        self.popup_background = background;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.size_hint.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.size_hint.height = height.into();
+        self
+    }
+
+    /// Get the indices of the options containing the filter, ignoring the case.
+    fn matches(&self) -> Vec<usize> {
+        let filter = self.state.filter.to_lowercase();
+        (0..self.options.len())
+            .filter(|&i| self.options[i].raw().to_lowercase().contains(&filter))
+            .collect()
+    }
+
+    fn options_width(&self) -> u16 {
+        self.options
+            .iter()
+            .map(|option| option.lines.first().map_or(0, Line::width))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn open(&mut self) {
+        self.state.open = true;
+        self.state.filter.clear();
+        self.state.offset = 0;
+        let cursor = self.selected.filter(|&index| index < self.options.len());
+        self.move_cursor(cursor.unwrap_or(0));
+    }
+
+    fn close(&mut self) {
+        self.state.open = false;
+        self.state.filter.clear();
+    }
+
+    /// Highlight an option among the matching ones and scroll to show it.
+    fn move_cursor(&mut self, cursor: usize) {
+        let count = self.matches().len();
+        let cursor = cursor.min(count.saturating_sub(1));
+        self.state.cursor = cursor;
+        self.state.offset = scroll::reveal(self.state.offset, cursor, count, MAX_ROWS);
+    }
+
+    fn scroll_to(&mut self, offset: usize) {
+        self.state.offset = scroll::clamp(offset, self.matches().len(), MAX_ROWS);
+    }
+
+    /// Choose the `i`-th matching option and close the popup.
+    fn select(&mut self, i: usize, shell: &mut Shell<Message>) {
+        if let (Some(on_select), Some(&index)) = (&self.on_select, self.matches().get(i)) {
+            shell.push(on_select(index));
+        }
+        self.close();
+    }
+
+    /// Process a key pressed while the popup is open.
+    fn process_key(&mut self, code: KeyCode, modifiers: KeyModifiers, shell: &mut Shell<Message>) {
+        let cursor = self.state.cursor;
+        match code {
+            KeyCode::Up => self.move_cursor(cursor.saturating_sub(1)),
+            KeyCode::Down => self.move_cursor(cursor + 1),
+            KeyCode::PageUp => self.move_cursor(cursor.saturating_sub(MAX_ROWS)),
+            KeyCode::PageDown => self.move_cursor(cursor + MAX_ROWS),
+            KeyCode::Enter => self.select(cursor, shell),
+            KeyCode::Esc => self.close(),
+            KeyCode::Backspace => {
+                self.state.filter.pop();
+                self.move_cursor(0);
+            }
+            KeyCode::Char(char)
+                if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.state.filter.push(char);
+                self.move_cursor(0);
+            }
+            _ => return,
+        }
+        shell.capture_event();
+    }
+}
+
+impl<Message> Widget<Message> for Select<Message> {
+    fn layout(&mut self, viewport: Area) {
+        self.bounds = viewport;
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() {
+            return;
+        }
+        match event {
+            Event::Key(event) if self.focused && event.kind != KeyEventKind::Release => {
+                if self.state.open {
+                    // Tab moves the focus away, which closes the popup.
+                    self.process_key(event.code, event.modifiers, shell);
+                } else if matches!(
+                    event.code,
+                    KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Down
+                ) {
+                    self.open();
+                    shell.capture_event();
+                }
+            }
+            Event::Mouse(event) => {
+                let in_popup = self.state.open && self.popup.contains(event.column, event.row);
+                match event.kind {
+                    MouseEventKind::Down(MouseButton::Left) if in_popup => {
+                        let row = (event.row - self.popup.y) as usize;
+                        self.select(self.state.offset + row, shell);
+                    }
+                    MouseEventKind::Down(MouseButton::Left)
+                        if self.bounds.contains(event.column, event.row) =>
+                    {
+                        if !self.focused {
+                            self.focused = true;
+                            shell.request_focus();
+                        }
+                        if self.state.open {
+                            self.close();
+                        } else {
+                            self.open();
+                        }
+                    }
+                    // A click anywhere else dismisses the popup, and goes through.
+                    MouseEventKind::Down(_) => {
+                        self.close();
+                        return;
+                    }
+                    MouseEventKind::ScrollUp if in_popup => {
+                        self.scroll_to(self.state.offset.saturating_sub(WHEEL_STEP))
+                    }
+                    MouseEventKind::ScrollDown if in_popup => {
+                        self.scroll_to(self.state.offset.saturating_add(WHEEL_STEP))
+                    }
+                    _ => return,
+                }
+                shell.capture_event();
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, _theme: &Theme) {
+        buffer.render_background(area, self.background);
+        if area.is_empty() {
+            return;
+        }
+        let mut style = self.style;
+        if self.focused {
+            style = style.foreground(Color::Focus);
+        }
+        let arrow = if buffer.is_ascii() { "v" } else { "▾" };
+        let text_area = Area::new(area.x, area.y, area.width.saturating_sub(2), 1);
+        let layout = TextLayout::new().wrap(false).truncate(true);
+        if self.state.open && !self.state.filter.is_empty() {
+            buffer.render_string(&self.state.filter, style, text_area, false);
+        } else if let Some(option) = self.selected.and_then(|index| self.options.get(index)) {
+            let line = StyledText::new(option.lines.first().cloned());
+            buffer.render_text(&line, style, text_area, &layout);
+        } else {
+            let style = style.foreground(Color::Disabled);
+            buffer.render_text(&self.placeholder, style, text_area, &layout);
+        }
+        let arrow_area = Area::new(area.x + area.width - 1, area.y, 1, 1);
+        buffer.render_string(arrow, style, arrow_area, false);
+    }
+
+    fn overlay(&mut self, viewport: Area) -> Option<Area> {
+        if !self.state.open {
+            return None;
+        }
+        let height = self.matches().len().clamp(1, MAX_ROWS) as u16;
+        let width = self.bounds.width.max(self.options_width());
+        let below = self.bounds.y.saturating_add(self.bounds.height);
+        let room_below = (viewport.y + viewport.height).saturating_sub(below);
+        let room_above = self.bounds.y.saturating_sub(viewport.y);
+        let y = if room_below < height && room_above >= height {
+            self.bounds.y - height
+        } else {
+            below
+        };
+        let x = self
+            .bounds
+            .x
+            .min((viewport.x + viewport.width).saturating_sub(width))
+            .max(viewport.x);
+        self.popup = Area::new(x, y, width, height);
+        Some(self.popup)
+    }
+
+    fn render_overlay(&self, area: Area, buffer: &mut Buffer, _theme: &Theme) {
+        buffer.clear_area(area);
+        buffer.render_background(area, self.popup_background);
+        let matches = self.matches();
+        if matches.is_empty() {
+            let style = self.style.foreground(Color::Disabled);
+            buffer.render_string("No matches", style, area, false);
+            return;
+        }
+        let layout = TextLayout::new().wrap(false).truncate(true);
+        let rows = area.y..area.y.saturating_add(area.height);
+        let options = matches.iter().enumerate().skip(self.state.offset);
+        for (y, (i, &index)) in rows.zip(options) {
+            let mut style = self.style;
+            if self.selected == Some(index) {
+                style = style.patch(self.selected_style);
+            }
+            if self.state.cursor == i {
+                style = style.patch(self.highlight_style).foreground(Color::Focus);
+            }
+            let row = Area::new(area.x, y, area.width, 1);
+            buffer.render_background(row, style.background);
+            let line = StyledText::new(self.options[index].lines.first().cloned());
+            buffer.render_text(&line, style, row, &layout);
+        }
+    }
+
+    fn size(&self) -> Size {
+        let placeholder = self.placeholder.lines.first().map_or(0, Line::width);
+        Size::new(self.options_width().max(placeholder) + 2, 1)
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        if !focused {
+            self.close();
+        }
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn take_state(&mut self) -> Option<Box<dyn Any>> {
+        Some(Box::new(std::mem::take(&mut self.state)))
+    }
+
+    fn restore(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<State>() {
+            self.state = *state;
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<Select<Message>> for Element<'a, Message> {
+    fn from(value: Select<Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use crate::{
+        buffer::buffer::Buffer, geometry::area::Area, style::theme::Theme, widget::widget::Widget,
+        widgets::testing::press,
+    };
+
+    use super::select;
+
+    const FRUITS: [&str; 5] = ["Apple", "Banana", "Cherry", "Mango", "Papaya"];
+
+    #[test]
+    fn filter() {
+        let mut select = select(FRUITS, Some(1)).on_select(|index| index);
+        select.layout(Area::new(0, 0, 8, 1));
+        select.set_focused(true);
+        let viewport = Area::from_wh(8, 8);
+
+        // The popup opens below, on the selected option.
+        press(&mut select, &[KeyCode::Enter]);
+        assert_eq!(select.overlay(viewport), Some(Area::new(0, 1, 8, 5)));
+        assert_eq!(
+            press(&mut select, &[KeyCode::Down, KeyCode::Enter]),
+            vec![2]
+        );
+        assert_eq!(select.overlay(viewport), None);
+
+        // At the bottom of the viewport, the popup opens above.
+        select.layout(Area::new(0, 7, 8, 1));
+        press(
+            &mut select,
+            &[KeyCode::Down, KeyCode::Char('A'), KeyCode::Char('n')],
+        );
+        let popup = select.overlay(viewport).unwrap();
+        assert_eq!(popup, Area::new(0, 5, 8, 2));
+        let mut buffer = Buffer::new(8, 8);
+        select.render(Area::new(0, 7, 8, 1), &mut buffer, &Theme::TOKYO_NIGHT);
+        select.render_overlay(popup, &mut buffer, &Theme::TOKYO_NIGHT);
+        assert_eq!(buffer.text(), "\n\n\n\n\nBanana\nMango\nAn     ▾");
+        assert_eq!(
+            press(&mut select, &[KeyCode::Down, KeyCode::Enter]),
+            vec![3]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use crossterm::event::Event;
+
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::{area::Area, length::Length, size::Size},
+    shell::Shell,
+    style::{color::Color, theme::Theme},
+    widget::{element::Element, widget::Widget},
+    widgets::{horizontal::VerticalAlignment, vertical::HorizontalAlignment},
+};
+
+/// A layer of a [`Stack`]: an element placed in the area of the stack.
+pub struct Layer<'a, Message> {
+    element: Element<'a, Message>,
+    horizontal: HorizontalAlignment,
+    vertical: VerticalAlignment,
+    /// The offset in columns and rows from the aligned position.
+    offset: (i16, i16),
+    bounds: Area,
+}
+
+impl<'a, Message> Layer<'a, Message> {
+    /// Create a layer placed at the top left corner of the stack.
+    pub fn new(element: impl Into<Element<'a, Message>>) -> Self {
+        Self {
+            element: element.into(),
+            horizontal: HorizontalAlignment::Start,
+            vertical: VerticalAlignment::Start,
+            offset: (0, 0),
+            bounds: Area::zeros(),
+        }
+    }
+
+    pub fn horizontal(mut self, alignment: HorizontalAlignment) -> Self {
+        self.horizontal = alignment;
+        self
+    }
+
+    pub fn vertical(mut self, alignment: VerticalAlignment) -> Self {
+        self.vertical = alignment;
+        self
+    }
+
+    /// Move the layer from its aligned position. The layer is clipped to the stack.
+    pub fn offset(mut self, x: i16, y: i16) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    fn layout(&mut self, viewport: Area) {
+        let widget = self.element.widget();
+        let resolve = |length, preferred: u16, available: u16| match length {
+            Length::Preferred => preferred.min(available),
+            Length::Fixed(length) => length.min(available),
+            _ => available,
+        };
+        let width = resolve(
+            widget.size_hint().width,
+            widget.size().width,
+            viewport.width,
+        );
+        let height = resolve(
+            widget.size_hint().height,
+            widget.height_for_width(width),
+            viewport.height,
+        );
+        let free = (viewport.width - width, viewport.height - height);
+        let x = match self.horizontal {
+            HorizontalAlignment::Start => 0,
+            HorizontalAlignment::Center => free.0 / 2,
+            HorizontalAlignment::End => free.0,
+        };
+        let y = match self.vertical {
+            VerticalAlignment::Start => 0,
+            VerticalAlignment::Center => free.1 / 2,
+            VerticalAlignment::End => free.1,
+        };
+        let place = |start: u16, position: u16, offset: i16| {
+            (start as i32 + position as i32 + offset as i32).clamp(0, u16::MAX as i32) as u16
+        };
+        self.bounds = Area::new(
+            place(viewport.x, x, self.offset.0),
+            place(viewport.y, y, self.offset.1),
+            width,
+            height,
+        )
+        .intersect(viewport);
+        self.element.widget_mut().layout(self.bounds);
+    }
+}
+
+/// Layers of elements drawn on top of each other in the same area, e.g. a badge over
+/// a label or a toolbar floating over a layout. The last layer is on top, and receives
+/// the events first.
+pub struct Stack<'a, Message> {
+    layers: Vec<Layer<'a, Message>>,
+    background: Option<Color>,
+    /// The size hints set by the user, otherwise the ones of the bottom layer are used.
+    width: Option<Length>,
+    height: Option<Length>,
+}
+
+#[macro_export]
+macro_rules! stack {
+    () => {
+        Stack::new()
+    };
+    ($($element:expr), + $(,)?) => {
+        Stack::with_children(vec![$($element),+])
+    };
+}
+
+impl<Message> Default for Stack<'_, Message> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, Message> Stack<'a, Message> {
+    pub fn new() -> Self {
+        Self {
+            layers: Vec::new(),
+            background: None,
+            width: None,
+            height: None,
+        }
+    }
+
+    /// Create a stack with a layer at the top left corner for each element.
+    pub fn with_children(children: Vec<Element<'a, Message>>) -> Self {
+        children
+            .into_iter()
+            .fold(Self::new(), |stack, child| stack.push(child))
+    }
+
+    /// Add an element on top, at the top left corner.
+    pub fn push(self, element: impl Into<Element<'a, Message>>) -> Self {
+        self.layer(Layer::new(element))
+    }
+
+    /// Add a layer on top.
+    pub fn layer(mut self, layer: Layer<'a, Message>) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = Some(width.into());
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = Some(height.into());
+        self
+    }
+}
+
+impl<Message> Widget<Message> for Stack<'_, Message> {
+    fn layout(&mut self, viewport: Area) {
+        for layer in self.layers.iter_mut() {
+            layer.layout(viewport);
+        }
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        for layer in self.layers.iter_mut().rev() {
+            if shell.is_event_captured() {
+                return;
+            }
+            layer
+                .element
+                .widget_mut()
+                .process_event(event.clone(), shell);
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, theme: &Theme) {
+        buffer.render_background(area, self.background);
+        for layer in self.layers.iter() {
+            layer.element.widget().render(layer.bounds, buffer, theme);
+        }
+    }
+
+    fn size(&self) -> Size {
+        self.layers
+            .iter()
+            .map(|layer| layer.element.widget().size())
+            .fold(Size::new(0, 0), |size, layer| {
+                Size::new(size.width.max(layer.width), size.height.max(layer.height))
+            })
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        let base = self.layers.first().map_or(Size::preferred(), |layer| {
+            layer.element.widget().size_hint()
+        });
+        Size::new(
+            self.width.unwrap_or(base.width),
+            self.height.unwrap_or(base.height),
+        )
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget<Message>> {
+        self.layers
+            .iter_mut()
+            .map(|layer| layer.element.widget_mut())
+            .collect()
+    }
+}
+
+impl<'a, Message: 'a> From<Stack<'a, Message>> for Element<'a, Message> {
+    fn from(value: Stack<'a, Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    use crate::{
+        buffer::buffer::Buffer,
+        geometry::{area::Area, length::Length},
+        shell::Shell,
+        style::theme::Theme,
+        widget::widget::Widget,
+        widgets::{
+            button::button, horizontal::VerticalAlignment, label::label,
+            vertical::HorizontalAlignment,
+        },
+    };
+
+    use super::{Layer, Stack};
+
+    #[test]
+    fn layers() {
+        let mut stack: Stack<()> = Stack::new()
+            .push(label("Inbox messages").width(Length::Fill))
+            .layer(
+                Layer::new(label("3"))
+                    .horizontal(HorizontalAlignment::End)
+                    .vertical(VerticalAlignment::End),
+            )
+            .layer(Layer::new(label("!")).offset(6, 1));
+        let area = Area::from_wh(14, 3);
+        let mut buffer = Buffer::new(14, 3);
+        stack.layout(area);
+        stack.render(area, &mut buffer, &Theme::TOKYO_NIGHT);
+        assert_eq!(buffer.text(), "Inbox messages\n      !\n             3");
+    }
+
+    #[test]
+    fn events() {
+        let mut stack = Stack::new()
+            .push(button("Below").on_click(1).width(Length::Fill))
+            .layer(Layer::new(button("Top").on_click(2)).offset(2, 0));
+        stack.layout(Area::from_wh(20, 3));
+
+        let mut shell = Shell::new();
+        for kind in [
+            MouseEventKind::Down(MouseButton::Left),
+            MouseEventKind::Up(MouseButton::Left),
+        ] {
+            let event = MouseEvent {
+                kind,
+                column: 3,
+                row: 1,
+                modifiers: KeyModifiers::NONE,
+            };
+            shell = Shell::new();
+            stack.process_event(Event::Mouse(event), &mut shell);
+        }
+        assert_eq!(shell.messages(), &vec![2]);
+    }
+}
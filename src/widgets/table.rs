@@ -0,0 +1,496 @@
+use std::any::Any;
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind};
+
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::{area::Area, length::Length, size::Size},
+    shell::Shell,
+    style::{color::Color, style::Style, theme::Theme},
+    text::{
+        layout::{Alignment, TextLayout},
+        Line, StyledText,
+    },
+    widget::{element::Element, widget::Widget},
+    widgets::scroll::{self, WHEEL_STEP},
+};
+
+/// The scroll of the rows of a [`Table`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct State {
+    /// The index of the first visible row.
+    offset: usize,
+    /// The selected row in the last layout, to scroll to a new selection.
+    selected: Option<usize>,
+}
+
+/// A column of a [`Table`], with its header.
+pub struct Column {
+    header: StyledText,
+    width: Length,
+    alignment: Alignment,
+}
+
+impl Column {
+    pub fn new(header: impl Into<StyledText>) -> Self {
+        Self {
+            header: header.into(),
+            width: Length::Fill,
+            alignment: Alignment::Left,
+        }
+    }
+
+    /// Set the width of the column, resolved with the other columns by
+    /// [`Length::resolve`]. The columns fill the table by default. A preferred column
+    /// fits its header and its widest visible cell, so its width may change while
+    /// scrolling.
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Set the alignment of the header and the cells in the column.
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+}
+
+/// Rows of cells under a header row, with a selectable row.
+///
+/// Only the visible rows are rendered, so a table can hold many rows. Each cell shows
+/// the first line of its text, truncated with `…` if it does not fit.
+pub struct Table<Message> {
+    columns: Vec<Column>,
+    rows: Vec<Vec<StyledText>>,
+    selected: Option<usize>,
+    on_select: Option<Box<dyn Fn(usize) -> Message>>,
+    column_spacing: u16,
+    striped: bool,
+    header_style: Style,
+    background: Option<Color>,
+    bounds: Area,
+    /// The widths of the columns resolved by the last layout.
+    widths: Vec<u16>,
+    state: State,
+    focused: bool,
+    size_hint: Size<Length>,
+}
+
+pub fn table<Message>(columns: impl IntoIterator<Item = Column>) -> Table<Message> {
+    Table::new(columns)
+}
+
+/// Get the width of the first line of a cell, the only one rendered.
+fn cell_width(cell: &StyledText) -> u16 {
+    cell.lines.first().map_or(0, Line::width)
+}
+
+impl<Message> Table<Message> {
+    pub fn new(columns: impl IntoIterator<Item = Column>) -> Self {
+        Self {
+            columns: columns.into_iter().collect(),
+            rows: Vec::new(),
+            selected: None,
+            on_select: None,
+            column_spacing: 1,
+            striped: true,
+            header_style: Style::new().foreground(Color::Primary).bold(),
+            background: None,
+            bounds: Area::zeros(),
+            widths: Vec::new(),
+            state: State::default(),
+            focused: false,
+            size_hint: Size::new(Length::Fill, Length::Fill),
+        }
+    }
+
+    /// Add a row of cells, one per column. Missing cells are left empty.
+    pub fn row<T: Into<StyledText>>(mut self, cells: impl IntoIterator<Item = T>) -> Self {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add rows of cells, see [`Self::row`].
+    pub fn rows<R, T>(self, rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = T>,
+        T: Into<StyledText>,
+    {
+        rows.into_iter().fold(self, |table, row| table.row(row))
+    }
+
+    /// Set the index of the selected row. The table scrolls to show a new selection.
+    pub fn selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Set the message produced with the index of a row selected with the keyboard or
+    /// the mouse.
+    pub fn on_select(mut self, on_select: impl Fn(usize) -> Message + 'static) -> Self {
+        self.on_select = Some(Box::new(on_select));
+        self
+    }
+
+    /// Set the number of columns between two columns.
+    pub fn column_spacing(mut self, spacing: u16) -> Self {
+        self.column_spacing = spacing;
+        self
+    }
+
+    /// Set whether every other row has the stripe background of the theme.
+    pub fn striped(mut self, striped: bool) -> Self {
+        self.striped = striped;
+        self
+    }
+
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.size_hint.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.size_hint.height = height.into();
+        self
+    }
+
+    /// Get the preferred widths of the columns. Only the preferred columns are measured
+    /// over the visible rows, the others prefer the width of their header.
+    fn preferred_widths(&self) -> Vec<u16> {
+        let start = self.state.offset.min(self.rows.len());
+        let end = (start + self.visible_rows()).min(self.rows.len());
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| match column.width {
+                Length::Fixed(width) => width,
+                Length::Preferred => self.rows[start..end]
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(cell_width)
+                    .fold(cell_width(&column.header), u16::max),
+                _ => cell_width(&column.header),
+            })
+            .collect()
+    }
+
+    fn spacing(&self) -> u16 {
+        let gaps = self.columns.len().saturating_sub(1) as u16;
+        gaps.saturating_mul(self.column_spacing)
+    }
+
+    /// The number of rows shown below the header.
+    fn visible_rows(&self) -> usize {
+        self.bounds.height.saturating_sub(1) as usize
+    }
+
+    fn scroll_to(&mut self, offset: usize) {
+        self.state.offset = scroll::clamp(offset, self.rows.len(), self.visible_rows());
+    }
+
+    fn select(&self, index: usize, shell: &mut Shell<Message>) {
+        if let Some(on_select) = &self.on_select {
+            if self.selected != Some(index) {
+                shell.push(on_select(index));
+            }
+        }
+    }
+
+    /// Render the cells of a row, or the headers, from the left of an area.
+    fn render_cells<'t>(
+        &self,
+        cells: impl Iterator<Item = &'t StyledText>,
+        style: Style,
+        area: Area,
+        buffer: &mut Buffer,
+    ) {
+        let mut x = area.x;
+        for ((cell, column), width) in cells.zip(&self.columns).zip(&self.widths) {
+            let line = StyledText::new(cell.lines.first().cloned());
+            let layout = TextLayout::new()
+                .wrap(false)
+                .truncate(true)
+                .alignment(column.alignment);
+            buffer.render_text(&line, style, Area::new(x, area.y, *width, 1), &layout);
+            x = x.saturating_add(width.saturating_add(self.column_spacing));
+        }
+    }
+}
+
+impl<Message> Widget<Message> for Table<Message> {
+    fn layout(&mut self, viewport: Area) {
+        self.bounds = viewport;
+        if self.selected != self.state.selected {
+            self.state.selected = self.selected;
+            if let Some(selected) = self.selected {
+                let (count, visible) = (self.rows.len(), self.visible_rows());
+                self.state.offset = scroll::reveal(self.state.offset, selected, count, visible);
+            }
+        }
+        self.scroll_to(self.state.offset);
+
+        let lengths = self.columns.iter().map(|column| column.width).collect();
+        let total = viewport.width.saturating_sub(self.spacing());
+        self.widths = Length::resolve(total, lengths, self.preferred_widths());
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() {
+            return;
+        }
+        match event {
+            Event::Key(event) if self.focused && event.kind != KeyEventKind::Release => {
+                let Some(last) = self.rows.len().checked_sub(1) else {
+                    return;
+                };
+                let page = self.visible_rows().max(1);
+                let index = match (event.code, self.selected) {
+                    (KeyCode::Up, Some(i)) => i.saturating_sub(1),
+                    (KeyCode::Down, Some(i)) => (i + 1).min(last),
+                    (KeyCode::PageUp, Some(i)) => i.saturating_sub(page),
+                    (KeyCode::PageDown, Some(i)) => (i + page).min(last),
+                    // Without a selection, start from the first visible row.
+                    (KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown, None) => {
+                        self.state.offset
+                    }
+                    (KeyCode::Home, _) => 0,
+                    (KeyCode::End, _) => last,
+                    _ => return,
+                };
+                shell.capture_event();
+                self.select(index, shell);
+            }
+            Event::Mouse(event) if self.bounds.contains(event.column, event.row) => {
+                match event.kind {
+                    MouseEventKind::ScrollUp => {
+                        self.scroll_to(self.state.offset.saturating_sub(WHEEL_STEP))
+                    }
+                    MouseEventKind::ScrollDown => {
+                        self.scroll_to(self.state.offset.saturating_add(WHEEL_STEP))
+                    }
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        if !self.focused {
+                            self.focused = true;
+                            shell.request_focus();
+                        }
+                        // The first row of the bounds is the header.
+                        let row = (event.row - self.bounds.y) as usize;
+                        if row > 0 {
+                            let index = self.state.offset + row - 1;
+                            if index < self.rows.len() {
+                                self.select(index, shell);
+                            }
+                        }
+                    }
+                    _ => return,
+                }
+                shell.capture_event();
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, _theme: &Theme) {
+        buffer.render_background(area, self.background);
+        if area.is_empty() {
+            return;
+        }
+        let headers = self.columns.iter().map(|column| &column.header);
+        self.render_cells(headers, self.header_style, area, buffer);
+
+        let rows = self.rows.iter().enumerate().skip(self.state.offset);
+        for (y, (index, row)) in (area.y + 1..area.y + area.height).zip(rows) {
+            let row_area = Area::new(area.x, y, area.width, 1);
+            let mut style = Style::new().foreground(Color::Text);
+            if self.selected == Some(index) {
+                buffer.render_background(row_area, Some(Color::Selection));
+                if self.focused {
+                    style = style.foreground(Color::Focus);
+                }
+            } else if self.striped && index % 2 == 1 {
+                buffer.render_background(row_area, Some(Color::Stripe));
+            }
+            self.render_cells(row.iter(), style, row_area, buffer);
+        }
+    }
+
+    fn size(&self) -> Size {
+        let width = self
+            .preferred_widths()
+            .into_iter()
+            .fold(self.spacing(), u16::saturating_add);
+        let height = (self.rows.len() + 1).min(u16::MAX as usize) as u16;
+        Size::new(width, height)
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn take_state(&mut self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.state))
+    }
+
+    fn restore(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<State>() {
+            self.state = *state;
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<Table<Message>> for Element<'a, Message> {
+    fn from(value: Table<Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+
+    use crate::{
+        buffer::buffer::Buffer,
+        geometry::{area::Area, length::Length},
+        shell::Shell,
+        style::{color::Color, theme::Theme},
+        text::layout::Alignment,
+        widget::widget::Widget,
+    };
+
+    use super::{table, Column, Table};
+
+    fn planets() -> Table<usize> {
+        table([
+            Column::new("Name").width(Length::Preferred),
+            Column::new("Moons").width(5).alignment(Alignment::Right),
+            Column::new("Notes").width(Length::Fill),
+        ])
+        .rows([
+            ["Mercury", "0", "closest to the sun"],
+            ["Venus", "0", "hottest"],
+            ["Earth", "1", "home"],
+            ["Mars", "2", "red"],
+        ])
+        .on_select(|index| index)
+    }
+
+    fn render(table: &mut Table<usize>, width: u16, height: u16) -> Buffer {
+        let mut buffer = Buffer::new(width, height);
+        let area = Area::from_wh(width, height);
+        table.layout(area);
+        table.render(area, &mut buffer, &Theme::TOKYO_NIGHT);
+        buffer
+    }
+
+    #[test]
+    fn render_rows() {
+        let buffer = render(&mut planets(), 24, 5);
+        assert_eq!(
+            buffer.text(),
+            [
+                "Name    Moons Notes",
+                "Mercury     0 closest t…",
+                "Venus       0 hottest",
+                "Earth       1 home",
+                "Mars        2 red",
+            ]
+            .join("\n")
+        );
+        let background = |y| buffer.get(0, y).style().background;
+        assert_eq!(background(1), None);
+        assert_eq!(background(2), Some(Color::Stripe));
+
+        // Only the rows around the selection are rendered and measured.
+        let buffer = render(&mut planets().selected(Some(3)), 24, 3);
+        assert_eq!(
+            buffer.text(),
+            "Name  Moons Notes\nEarth     1 home\nMars      2 red"
+        );
+        assert_eq!(buffer.get(0, 2).style().background, Some(Color::Selection));
+    }
+
+    #[test]
+    fn select() {
+        let mut table = planets().selected(Some(1));
+        table.layout(Area::from_wh(24, 3));
+        table.set_focused(true);
+
+        let mut shell = Shell::new();
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        table.process_event(Event::Key(down), &mut shell);
+        assert_eq!(shell.messages(), &vec![2]);
+        assert!(shell.is_event_captured());
+
+        // Scroll down to the last rows and click the second visible one.
+        let mouse = |kind, row| {
+            Event::Mouse(MouseEvent {
+                kind,
+                column: 2,
+                row,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+        let mut shell = Shell::new();
+        table.process_event(mouse(MouseEventKind::ScrollDown, 1), &mut shell);
+        let mut shell = Shell::new();
+        table.process_event(
+            mouse(MouseEventKind::Down(MouseButton::Left), 2),
+            &mut shell,
+        );
+        assert_eq!(shell.messages(), &vec![3]);
+
+        // A click on the header of a scrolled table selects nothing.
+        let mut shell = Shell::new();
+        table.process_event(
+            mouse(MouseEventKind::Down(MouseButton::Left), 0),
+            &mut shell,
+        );
+        assert!(shell.messages().is_empty());
+        assert!(shell.is_event_captured());
+    }
+
+    #[test]
+    fn many_rows() {
+        // The preferred column is measured over the visible rows only.
+        let rows = (0..100_000).map(|i| {
+            let name = if i == 99_999 { "Last of many" } else { "Row" };
+            [i.to_string(), name.to_string()]
+        });
+        let mut table = table::<usize>([
+            Column::new("Name").width(Length::Preferred),
+            Column::new("Id"),
+        ])
+        .rows(rows.map(|[id, name]| [name, id]));
+        assert_eq!(render(&mut table, 16, 3).text(), "Name Id\nRow  0\nRow  1");
+        let mut table = table.selected(Some(99_999));
+        assert_eq!(
+            render(&mut table, 18, 3).text(),
+            "Name         Id\nRow          99998\nLast of many 99999"
+        );
+    }
+}
@@ -0,0 +1,494 @@
+use std::any::Any;
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::{area::Area, length::Length, size::Size},
+    shell::Shell,
+    style::{color::Color, style::Style, theme::Theme},
+    text::{layout::TextLayout, Line, StyledText},
+    widget::{element::Element, widget::Widget},
+};
+
+/// The scroll of the header strip of [`Tabs`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct State {
+    /// The number of columns of the header strip scrolled out on the left.
+    offset: u16,
+}
+
+/// A tab of [`Tabs`]: a title in the header strip and the content shown when active.
+struct Tab<'a, Message> {
+    title: StyledText,
+    element: Element<'a, Message>,
+}
+
+/// Elements shown one at a time under a header strip of titles.
+///
+/// The active tab is changed by clicking a title, with Ctrl+Tab and Ctrl+Shift+Tab, or
+/// with the arrow and number keys while the header strip is focused. The header strip
+/// scrolls to show the active title when the titles do not fit. The internal states of
+/// the contents, e.g. a scroll offset, are kept while their tabs are inactive.
+pub struct Tabs<'a, Message> {
+    tabs: Vec<Tab<'a, Message>>,
+    active: usize,
+    on_change: Option<Box<dyn Fn(usize) -> Message>>,
+    background: Option<Color>,
+    bounds: Area,
+    state: State,
+    focused: bool,
+    size_hint: Size<Length>,
+}
+
+pub fn tabs<'a, Message>() -> Tabs<'a, Message> {
+    Tabs::new()
+}
+
+impl<Message> Default for Tabs<'_, Message> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, Message> Tabs<'a, Message> {
+    pub fn new() -> Self {
+        Self {
+            tabs: Vec::new(),
+            active: 0,
+            on_change: None,
+            background: None,
+            bounds: Area::zeros(),
+            state: State::default(),
+            focused: false,
+            size_hint: Size::new(Length::Fill, Length::Fill),
+        }
+    }
+
+    /// Add a tab at the end. Only the first line of the title is shown.
+    pub fn push(
+        mut self,
+        title: impl Into<StyledText>,
+        element: impl Into<Element<'a, Message>>,
+    ) -> Self {
+        self.tabs.push(Tab {
+            title: title.into(),
+            element: element.into(),
+        });
+        self
+    }
+
+    /// Set the index of the active tab.
+    pub fn active(mut self, active: usize) -> Self {
+        self.active = active;
+        self
+    }
+
+    /// Set the message produced with the index of the tab chosen by the user.
+    pub fn on_change(mut self, on_change: impl Fn(usize) -> Message + 'static) -> Self {
+        self.on_change = Some(Box::new(on_change));
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.size_hint.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.size_hint.height = height.into();
+        self
+    }
+
+    fn active_tab(&self) -> Option<&Tab<'a, Message>> {
+        self.tabs.get(self.active)
+    }
+
+    /// Get the start and the end columns of the titles in the header strip. The titles
+    /// are padded with a space on each side, and separated by a column.
+    fn title_ranges(&self) -> Vec<(u16, u16)> {
+        let mut x = 0u16;
+        self.tabs
+            .iter()
+            .map(|tab| {
+                let width = tab.title.lines.first().map_or(0, Line::width) + 2;
+                let range = (x, x.saturating_add(width));
+                x = range.1.saturating_add(1);
+                range
+            })
+            .collect()
+    }
+
+    /// Get the area of the header strip showing the titles, without the scroll
+    /// indicators shown if the titles do not fit.
+    fn strip(&self) -> Area {
+        let width = self.title_ranges().last().map_or(0, |range| range.1);
+        let overflow = (width > self.bounds.width) as u16;
+        Area::new(
+            self.bounds.x + overflow,
+            self.bounds.y,
+            self.bounds.width.saturating_sub(overflow * 2),
+            self.bounds.height.min(1),
+        )
+    }
+
+    fn content_area(&self, area: Area) -> Area {
+        Area::new(
+            area.x,
+            area.y.saturating_add(1),
+            area.width,
+            area.height.saturating_sub(1),
+        )
+    }
+
+    fn change(&self, index: usize, shell: &mut Shell<Message>) {
+        if let Some(on_change) = &self.on_change {
+            if index != self.active && index < self.tabs.len() {
+                shell.push(on_change(index));
+            }
+        }
+    }
+}
+
+impl<Message> Widget<Message> for Tabs<'_, Message> {
+    fn layout(&mut self, viewport: Area) {
+        self.bounds = viewport;
+        let content = self.content_area(viewport);
+        if let Some(tab) = self.tabs.get_mut(self.active) {
+            tab.element.widget_mut().layout(content);
+        }
+
+        // Scroll the header strip to show the active title.
+        let strip = self.strip();
+        let ranges = self.title_ranges();
+        let width = ranges.last().map_or(0, |range| range.1);
+        if let Some(&(start, end)) = ranges.get(self.active) {
+            if start < self.state.offset {
+                self.state.offset = start;
+            } else if end > self.state.offset + strip.width {
+                self.state.offset = end.saturating_sub(strip.width);
+            }
+        }
+        self.state.offset = self.state.offset.min(width.saturating_sub(strip.width));
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() {
+            return;
+        }
+        if let Some(tab) = self.tabs.get_mut(self.active) {
+            tab.element.widget_mut().process_event(event.clone(), shell);
+            if shell.is_event_captured() {
+                return;
+            }
+        }
+        let count = self.tabs.len();
+        if count == 0 {
+            return;
+        }
+        let next = (self.active + 1) % count;
+        let previous = (self.active + count - 1) % count;
+        match event {
+            Event::Key(event) if event.kind != KeyEventKind::Release => {
+                let control = event.modifiers.contains(KeyModifiers::CONTROL);
+                let index = match event.code {
+                    KeyCode::Tab if control => next,
+                    KeyCode::BackTab if control => previous,
+                    KeyCode::Right if self.focused => next,
+                    KeyCode::Left if self.focused => previous,
+                    KeyCode::Char(char @ '1'..='9') if self.focused => char as usize - '1' as usize,
+                    _ => return,
+                };
+                shell.capture_event();
+                self.change(index, shell);
+            }
+            Event::Mouse(event) if self.strip().contains(event.column, event.row) => {
+                let strip = self.strip();
+                let ranges = self.title_ranges();
+                let width = ranges.last().map_or(0, |range| range.1);
+                let max_offset = width.saturating_sub(strip.width);
+                match event.kind {
+                    MouseEventKind::ScrollUp | MouseEventKind::ScrollLeft => {
+                        self.state.offset = self.state.offset.saturating_sub(1)
+                    }
+                    MouseEventKind::ScrollDown | MouseEventKind::ScrollRight => {
+                        self.state.offset = (self.state.offset + 1).min(max_offset)
+                    }
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        let x = event.column - strip.x + self.state.offset;
+                        let index = ranges
+                            .iter()
+                            .position(|&(start, end)| (start..end).contains(&x));
+                        if let Some(index) = index {
+                            self.change(index, shell);
+                        }
+                    }
+                    _ => return,
+                }
+                shell.capture_event();
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, theme: &Theme) {
+        buffer.render_background(area, self.background);
+        if area.is_empty() {
+            return;
+        }
+
+        // Render the whole header strip, then the visible part of it.
+        let ranges = self.title_ranges();
+        let width = ranges.last().map_or(0, |range| range.1);
+        let mut header = Buffer::new(width, 1);
+        header.set_color_system(buffer.color_system());
+        let layout = TextLayout::new().wrap(false);
+        let separator = Style::new().foreground(Color::Border);
+        for (i, (tab, &(start, end))) in self.tabs.iter().zip(&ranges).enumerate() {
+            let mut style = Style::new().foreground(Color::Text);
+            if i == self.active {
+                style = style
+                    .foreground(Color::Primary)
+                    .background(Color::Selection)
+                    .bold();
+                if self.focused {
+                    style = style.foreground(Color::Focus);
+                }
+                header.render_background(Area::new(start, 0, end - start, 1), style.background);
+            }
+            let title = StyledText::new(tab.title.lines.first().cloned());
+            header.render_text(
+                &title,
+                style,
+                Area::new(start + 1, 0, end - start - 2, 1),
+                &layout,
+            );
+            if i + 1 < self.tabs.len() {
+                header.render_string("│", separator, Area::new(end, 0, 1, 1), false);
+            }
+        }
+        let strip = if width > area.width {
+            let indicator = Style::new().foreground(Color::Border);
+            if self.state.offset > 0 {
+                buffer.render_string("‹", indicator, Area::new(area.x, area.y, 1, 1), false);
+            }
+            if self.state.offset + area.width.saturating_sub(2) < width {
+                let x = area.x + area.width - 1;
+                buffer.render_string("›", indicator, Area::new(x, area.y, 1, 1), false);
+            }
+            Area::new(area.x + 1, area.y, area.width.saturating_sub(2), 1)
+        } else {
+            Area::new(area.x, area.y, area.width, 1)
+        };
+        let window = header.cut(Area::new(self.state.offset, 0, strip.width, 1));
+        buffer.render(strip.x, strip.y, &window);
+
+        if let Some(tab) = self.active_tab() {
+            tab.element
+                .widget()
+                .render(self.content_area(area), buffer, theme);
+        }
+    }
+
+    fn size(&self) -> Size {
+        let header = self.title_ranges().last().map_or(0, |range| range.1);
+        let content = self
+            .active_tab()
+            .map_or(Size::new(0, 0), |tab| tab.element.widget().size());
+        Size::new(header.max(content.width), content.height.saturating_add(1))
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget<Message>> {
+        match self.tabs.get_mut(self.active) {
+            Some(tab) => vec![tab.element.widget_mut()],
+            None => Vec::new(),
+        }
+    }
+
+    fn all_children_mut(&mut self) -> Vec<&mut dyn Widget<Message>> {
+        self.tabs
+            .iter_mut()
+            .map(|tab| tab.element.widget_mut())
+            .collect()
+    }
+
+    fn take_state(&mut self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.state))
+    }
+
+    fn restore(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<State>() {
+            self.state = *state;
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<Tabs<'a, Message>> for Element<'a, Message> {
+    fn from(value: Tabs<'a, Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+
+    use crate::{
+        backend::test_backend::TestBackend,
+        buffer::buffer::Buffer,
+        command::Command,
+        geometry::area::Area,
+        shell::Shell,
+        style::theme::Theme,
+        widget::{
+            app::{App, Program},
+            element::Element,
+            widget::Widget,
+        },
+        widgets::{label::label, list::list},
+    };
+
+    use super::{tabs, Tabs};
+
+    fn screens(active: usize) -> Tabs<'static, usize> {
+        tabs()
+            .push("Home", label("Welcome"))
+            .push("Settings", label("Options"))
+            .push("About", label("Version 1"))
+            .active(active)
+            .on_change(|index| index)
+    }
+
+    fn render(tabs: &mut Tabs<usize>, width: u16) -> String {
+        let mut buffer = Buffer::new(width, 2);
+        tabs.layout(Area::from_wh(width, 2));
+        tabs.render(Area::from_wh(width, 2), &mut buffer, &Theme::TOKYO_NIGHT);
+        buffer.text()
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(
+            render(&mut screens(0), 30),
+            " Home │ Settings │ About\nWelcome"
+        );
+        // The strip scrolls to show the active title.
+        assert_eq!(render(&mut screens(0), 12), "  Home │ Se›\nWelcome");
+        assert_eq!(render(&mut screens(2), 12), "‹s │ About\nVersion 1");
+    }
+
+    #[test]
+    fn switch() {
+        let mut tabs = screens(0);
+        tabs.layout(Area::from_wh(30, 2));
+        let press = |tabs: &mut Tabs<usize>, code, modifiers| {
+            let mut shell = Shell::new();
+            let event = KeyEvent::new(code, modifiers);
+            tabs.process_event(Event::Key(event), &mut shell);
+            shell.messages().clone()
+        };
+        assert_eq!(
+            press(&mut tabs, KeyCode::Tab, KeyModifiers::CONTROL),
+            vec![1]
+        );
+        assert_eq!(
+            press(&mut tabs, KeyCode::BackTab, KeyModifiers::CONTROL),
+            vec![2]
+        );
+        // The number keys only switch while the header strip is focused.
+        assert!(press(&mut tabs, KeyCode::Char('3'), KeyModifiers::NONE).is_empty());
+        tabs.set_focused(true);
+        assert_eq!(
+            press(&mut tabs, KeyCode::Char('3'), KeyModifiers::NONE),
+            vec![2]
+        );
+
+        let mut shell = Shell::new();
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 10,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+        tabs.process_event(Event::Mouse(click), &mut shell);
+        assert_eq!(shell.messages(), &vec![1]);
+    }
+
+    struct Planets {
+        active: usize,
+    }
+
+    impl<'a> Program<'a, usize> for Planets {
+        fn update(&mut self, message: usize) -> Command<usize> {
+            self.active = message;
+            Command::none()
+        }
+
+        fn view(&self) -> Element<'a, usize> {
+            tabs()
+                .push("Inner", list(["Mercury", "Venus", "Earth", "Mars"]))
+                .push("Outer", list(["Jupiter", "Saturn", "Uranus", "Neptune"]))
+                .active(self.active)
+                .on_change(|index| index)
+                .into()
+        }
+    }
+
+    #[test]
+    fn keep_states() {
+        let mut app = App::with_backend(Planets { active: 0 }, TestBackend::new(16, 3));
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 1,
+            row: 1,
+            modifiers: KeyModifiers::NONE,
+        };
+        let key = |code, modifiers| Event::Key(KeyEvent::new(code, modifiers));
+        app.backend_mut()
+            .push_event(Event::Mouse(click))
+            .push_event(key(KeyCode::End, KeyModifiers::NONE));
+        app.step().unwrap();
+        app.step().unwrap();
+        assert_eq!(app.backend().buffer().text(), " Inner │ Outer\nEarth\nMars");
+
+        // The list of the other tab does not get the scroll of the first one, which is
+        // restored when switching back.
+        app.backend_mut()
+            .push_event(key(KeyCode::Tab, KeyModifiers::CONTROL));
+        app.step().unwrap();
+        app.step().unwrap();
+        assert_eq!(
+            app.backend().buffer().text(),
+            " Inner │ Outer\nJupiter\nSaturn"
+        );
+        app.backend_mut()
+            .push_event(key(KeyCode::Tab, KeyModifiers::CONTROL));
+        app.step().unwrap();
+        app.step().unwrap();
+        assert_eq!(app.program().active, 0);
+        assert_eq!(app.backend().buffer().text(), " Inner │ Outer\nEarth\nMars");
+    }
+}
@@ -0,0 +1,20 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{shell::Shell, widget::widget::Widget};
+
+/// Press keys one after the other on a widget, and get the messages produced.
+pub(crate) fn press<Message: Clone>(
+    widget: &mut dyn Widget<Message>,
+    codes: &[KeyCode],
+) -> Vec<Message> {
+    let mut messages = Vec::new();
+    for code in codes {
+        let mut shell = Shell::new();
+        widget.process_event(
+            Event::Key(KeyEvent::new(*code, KeyModifiers::NONE)),
+            &mut shell,
+        );
+        messages.extend(shell.messages().iter().cloned());
+    }
+    messages
+}
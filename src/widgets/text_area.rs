@@ -0,0 +1,732 @@
+use std::any::Any;
+
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    buffer::{buffer::Buffer, pixel::Pixel},
+    geometry::{area::Area, length::Length, size::Size, spacing::Spacing},
+    shell::Shell,
+    style::{color::Color, style::Style, theme::Theme},
+    text::{grapheme_width, layout::TextLayout, StyledText, Text},
+    widget::{element::Element, widget::Widget},
+    widgets::scroll::{self, WHEEL_STEP},
+};
+
+/// The maximum number of undo steps kept.
+const HISTORY_SIZE: usize = 100;
+
+/// A row of the wrapped content, as a range of bytes of the value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Row {
+    start: usize,
+    end: usize,
+    /// Whether the row is the last one of its line, i.e. `end` is a line break or the end
+    /// of the value.
+    last: bool,
+}
+
+/// The cursor, the selection, the scroll and the history of a [`TextArea`].
+#[derive(Debug, Clone, Default)]
+struct State {
+    /// The value when the state was taken, to detect changes made by the program.
+    value: String,
+    /// The byte index of the cursor in the value, always on a grapheme boundary.
+    cursor: usize,
+    /// The other end of the selection.
+    anchor: Option<usize>,
+    /// The column kept when moving the cursor up and down.
+    column: Option<u16>,
+    /// The number of rows scrolled out on the top.
+    scroll: usize,
+    dragging: bool,
+    /// Whether the last edit was typing, consecutive typing is undone at once.
+    typing: bool,
+    undo: Vec<(String, usize)>,
+    redo: Vec<(String, usize)>,
+}
+
+pub struct TextArea<Message> {
+    value: String,
+    style: Style,
+    selection_style: Style,
+    padding: Spacing,
+    background: Option<Color>,
+    on_change: Option<Box<dyn Fn(String) -> Message>>,
+    state: State,
+    focused: bool,
+    bounds: Area,
+    size_hint: Size<Length>,
+}
+
+pub fn text_area<Message>(value: impl Into<String>) -> TextArea<Message> {
+    TextArea::new(value)
+}
+
+/// Wrap the value into rows no wider than `width`, on word boundaries like the
+/// [`TextLayout`] of the labels. A row goes on to the start of the next one, so the
+/// spaces dropped where a line is broken belong to the row before the break.
+fn wrap(value: &str, width: u16) -> Vec<Row> {
+    let layout = TextLayout::new();
+    let mut rows = Vec::new();
+    let mut line_start = 0;
+    for line in value.split('\n') {
+        let text = StyledText::from(line);
+        let mut graphemes = line.grapheme_indices(true);
+        let mut start = line_start;
+        for (i, glyphs) in layout
+            .rows(&text, Style::new(), Some(width), None)
+            .iter()
+            .enumerate()
+        {
+            // The glyphs follow the graphemes of the line, less the dropped ones.
+            let mut first = None;
+            for glyph in glyphs {
+                let index = graphemes
+                    .by_ref()
+                    .find(|(_, grapheme)| *grapheme == glyph.grapheme)
+                    .map_or(line.len(), |(index, _)| index);
+                first.get_or_insert(index);
+            }
+            let index = first.unwrap_or(line.len());
+            if i > 0 {
+                rows.push(Row {
+                    start,
+                    end: line_start + index,
+                    last: false,
+                });
+                start = line_start + index;
+            }
+        }
+        rows.push(Row {
+            start,
+            end: line_start + line.len(),
+            last: true,
+        });
+        line_start += line.len() + 1;
+    }
+    rows
+}
+
+/// Push a step on a history, dropping the oldest one past [`HISTORY_SIZE`].
+fn record(history: &mut Vec<(String, usize)>, step: (String, usize)) {
+    history.push(step);
+    if history.len() > HISTORY_SIZE {
+        history.remove(0);
+    }
+}
+
+impl<Message> TextArea<Message> {
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        Self {
+            state: State {
+                value: value.clone(),
+                cursor: value.len(),
+                ..Default::default()
+            },
+            value,
+            style: Style::new().foreground(Color::Text),
+            selection_style: Style::new()
+                .foreground(Color::Background)
+                .background(Color::Primary),
+            padding: Spacing::horizontal(1),
+            background: Some(Color::Background),
+            on_change: None,
+            focused: false,
+            bounds: Area::zeros(),
+            size_hint: Size::new(Length::Fill, Length::Preferred),
+        }
+    }
+
+    /// Set the message produced with the full content when the content is edited.
+    /// The text area is read-only without it.
+    pub fn on_change(mut self, on_change: impl Fn(String) -> Message + 'static) -> Self {
+        self.on_change = Some(Box::new(on_change));
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn selection_style(mut self, style: Style) -> Self {
+        self.selection_style = style;
+        self
+    }
+
+    pub fn padding(mut self, padding: impl Into<Spacing>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn width(mut self, width: Length) -> Self {
+        self.size_hint.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Length) -> Self {
+        self.size_hint.height = height;
+        self
+    }
+
+    fn content_area(&self) -> Area {
+        self.bounds.shrink(self.padding)
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        match self.content_area().width {
+            0 => wrap(&self.value, u16::MAX),
+            width => wrap(&self.value, width),
+        }
+    }
+
+    /// The row and column of a byte index.
+    fn locate(&self, rows: &[Row], index: usize) -> (usize, u16) {
+        let row = rows
+            .iter()
+            .position(|row| index < row.end || (index == row.end && row.last))
+            .unwrap_or(rows.len() - 1);
+        let start = rows[row].start.min(index);
+        let column = self.value[start..index]
+            .graphemes(true)
+            .map(grapheme_width)
+            .sum();
+        (row, column)
+    }
+
+    /// The byte index of the grapheme displayed at a column of a row.
+    fn index_at(&self, row: Row, column: u16) -> usize {
+        let mut x = 0;
+        let mut last = row.start;
+        for (index, grapheme) in self.value[row.start..row.end].grapheme_indices(true) {
+            x += grapheme_width(grapheme);
+            if column < x {
+                return row.start + index;
+            }
+            last = row.start + index;
+        }
+        // The end of a wrapped row is the start of the next row.
+        if row.last {
+            row.end
+        } else {
+            last
+        }
+    }
+
+    /// The selected range of bytes, if not empty.
+    fn selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.state.anchor?;
+        let cursor = self.state.cursor;
+        match anchor.cmp(&cursor) {
+            std::cmp::Ordering::Less => Some((anchor, cursor)),
+            std::cmp::Ordering::Greater => Some((cursor, anchor)),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// Replace the bytes between `start` and `end` with a string, and put the cursor
+    /// after it. Returns whether the value has been changed.
+    fn replace(&mut self, start: usize, end: usize, string: &str, typing: bool) -> bool {
+        if start == end && string.is_empty() {
+            return false;
+        }
+        if !(typing && self.state.typing) {
+            record(
+                &mut self.state.undo,
+                (self.value.clone(), self.state.cursor),
+            );
+        }
+        self.state.redo.clear();
+        self.state.typing = typing;
+        self.value.replace_range(start..end, string);
+        self.state.cursor = start + string.len();
+        self.state.anchor = None;
+        self.state.column = None;
+        true
+    }
+
+    /// Replace the selection, or insert at the cursor.
+    fn insert(&mut self, string: &str, typing: bool) -> bool {
+        let string: String = string
+            .chars()
+            .filter(|char| *char == '\n' || !char.is_control())
+            .collect();
+        let (start, end) = self
+            .selection()
+            .unwrap_or((self.state.cursor, self.state.cursor));
+        self.replace(start, end, &string, typing)
+    }
+
+    /// Remove the selection, or the bytes between the cursor and `index`.
+    fn remove_to(&mut self, index: usize) -> bool {
+        let (start, end) = self.selection().unwrap_or(if index < self.state.cursor {
+            (index, self.state.cursor)
+        } else {
+            (self.state.cursor, index)
+        });
+        self.replace(start, end, "", false)
+    }
+
+    fn undo(&mut self) -> bool {
+        let Some((value, cursor)) = self.state.undo.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.value, value);
+        self.state.redo.push((current, self.state.cursor));
+        self.state.cursor = cursor;
+        self.state.anchor = None;
+        self.state.typing = false;
+        true
+    }
+
+    fn redo(&mut self) -> bool {
+        let Some((value, cursor)) = self.state.redo.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.value, value);
+        record(&mut self.state.undo, (current, self.state.cursor));
+        self.state.cursor = cursor;
+        self.state.anchor = None;
+        self.state.typing = false;
+        true
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.value[..self.state.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.value[self.state.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.state.cursor, |grapheme| {
+                self.state.cursor + grapheme.len()
+            })
+    }
+
+    fn previous_word(&self) -> usize {
+        self.value[..self.state.cursor]
+            .split_word_bound_indices()
+            .rev()
+            .find(|(_, word)| !word.trim().is_empty())
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_word(&self) -> usize {
+        self.value[self.state.cursor..]
+            .split_word_bound_indices()
+            .find(|(_, word)| !word.trim().is_empty())
+            .map_or(self.value.len(), |(index, word)| {
+                self.state.cursor + index + word.len()
+            })
+    }
+
+    /// The byte index after moving the cursor by a number of rows, keeping the column.
+    fn vertical_target(&mut self, rows: isize) -> usize {
+        let all_rows = self.rows();
+        let (row, column) = self.locate(&all_rows, self.state.cursor);
+        let column = *self.state.column.get_or_insert(column);
+        let target = row as isize + rows;
+        if target < 0 {
+            0
+        } else if target as usize >= all_rows.len() {
+            self.value.len()
+        } else {
+            self.index_at(all_rows[target as usize], column)
+        }
+    }
+
+    /// Process a key press, returns whether the value has been changed.
+    fn process_key(&mut self, event: KeyEvent) -> bool {
+        let editable = self.on_change.is_some();
+        let control = event.modifiers.contains(KeyModifiers::CONTROL);
+        let shift = event.modifiers.contains(KeyModifiers::SHIFT);
+        let page = self.content_area().height.max(1) as isize;
+
+        let target = match event.code {
+            KeyCode::Char(char)
+                if editable && !control && !event.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                return self.insert(char.encode_utf8(&mut [0; 4]), !char.is_whitespace());
+            }
+            KeyCode::Enter if editable => return self.insert("\n", false),
+            KeyCode::Backspace if editable => {
+                let index = if control {
+                    self.previous_word()
+                } else {
+                    self.previous_boundary()
+                };
+                return self.remove_to(index);
+            }
+            KeyCode::Delete if editable => {
+                let index = if control {
+                    self.next_word()
+                } else {
+                    self.next_boundary()
+                };
+                return self.remove_to(index);
+            }
+            KeyCode::Char('z') if editable && control => return self.undo(),
+            KeyCode::Char('y') if editable && control => return self.redo(),
+            KeyCode::Char('a') if control => {
+                self.state.anchor = Some(0);
+                self.state.cursor = self.value.len();
+                return false;
+            }
+            KeyCode::Left if control => self.previous_word(),
+            KeyCode::Left => self.previous_boundary(),
+            KeyCode::Right if control => self.next_word(),
+            KeyCode::Right => self.next_boundary(),
+            KeyCode::Home if control => 0,
+            KeyCode::End if control => self.value.len(),
+            KeyCode::Home | KeyCode::End => {
+                let rows = self.rows();
+                let (row, _) = self.locate(&rows, self.state.cursor);
+                if event.code == KeyCode::Home {
+                    rows[row].start
+                } else {
+                    self.index_at(rows[row], u16::MAX)
+                }
+            }
+            KeyCode::Up => self.vertical_target(-1),
+            KeyCode::Down => self.vertical_target(1),
+            KeyCode::PageUp => self.vertical_target(-page),
+            KeyCode::PageDown => self.vertical_target(page),
+            _ => return false,
+        };
+
+        if !matches!(
+            event.code,
+            KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown
+        ) {
+            self.state.column = None;
+        }
+        if shift {
+            self.state.anchor.get_or_insert(self.state.cursor);
+        } else {
+            self.state.anchor = None;
+        }
+        self.state.cursor = target;
+        self.state.typing = false;
+        false
+    }
+
+    /// The byte index of the grapheme displayed at a position of the screen.
+    fn index_at_position(&self, x: u16, y: u16) -> usize {
+        let area = self.content_area();
+        let rows = self.rows();
+        let row = if y < area.y {
+            self.state.scroll.saturating_sub(1)
+        } else {
+            self.state.scroll + (y - area.y) as usize
+        };
+        let row = rows[row.min(rows.len() - 1)];
+        self.index_at(row, x.saturating_sub(area.x))
+    }
+
+    fn process_mouse(&mut self, event: MouseEvent, shell: &mut Shell<Message>) {
+        let inside = self.bounds.contains(event.column, event.row);
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) if inside => {
+                if !self.focused {
+                    self.focused = true;
+                    shell.request_focus();
+                }
+                let index = self.index_at_position(event.column, event.row);
+                if event.modifiers.contains(KeyModifiers::SHIFT) {
+                    self.state.anchor.get_or_insert(self.state.cursor);
+                } else {
+                    self.state.anchor = Some(index);
+                }
+                self.state.cursor = index;
+                self.state.dragging = true;
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.state.dragging => {
+                self.state.cursor = self.index_at_position(event.column, event.row);
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.state.dragging => {
+                self.state.dragging = false;
+            }
+            MouseEventKind::ScrollUp if inside => {
+                self.state.scroll = self.state.scroll.saturating_sub(WHEEL_STEP);
+                shell.capture_event();
+                return;
+            }
+            MouseEventKind::ScrollDown if inside => {
+                let (count, visible) = (self.rows().len(), self.content_area().height as usize);
+                self.state.scroll = scroll::clamp(self.state.scroll + WHEEL_STEP, count, visible);
+                shell.capture_event();
+                return;
+            }
+            _ => return,
+        }
+        self.state.column = None;
+        self.state.typing = false;
+        shell.capture_event();
+        self.scroll_to_cursor();
+    }
+
+    /// Scroll vertically, so that the cursor is visible.
+    fn scroll_to_cursor(&mut self) {
+        let height = self.content_area().height as usize;
+        let rows = self.rows();
+        let (row, _) = self.locate(&rows, self.state.cursor);
+        self.state.scroll = scroll::reveal(self.state.scroll, row, rows.len(), height);
+    }
+}
+
+impl<Message> Widget<Message> for TextArea<Message> {
+    fn layout(&mut self, viewport: Area) {
+        self.bounds = viewport;
+        // Keep the scroll of the mouse wheel, the cursor is only revealed when it moves.
+        let (count, visible) = (self.rows().len(), self.content_area().height as usize);
+        self.state.scroll = scroll::clamp(self.state.scroll, count, visible);
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() {
+            return;
+        }
+        let changed = match event {
+            Event::Key(event) if self.focused => {
+                if matches!(event.code, KeyCode::Tab | KeyCode::BackTab | KeyCode::Esc) {
+                    return;
+                }
+                shell.capture_event();
+                if event.kind == KeyEventKind::Release {
+                    return;
+                }
+                self.process_key(event)
+            }
+            Event::Paste(string) if self.focused && self.on_change.is_some() => {
+                shell.capture_event();
+                self.insert(&string, false)
+            }
+            Event::Mouse(event) => {
+                self.process_mouse(event, shell);
+                return;
+            }
+            _ => return,
+        };
+        if changed {
+            if let Some(on_change) = &self.on_change {
+                shell.push(on_change(self.value.clone()));
+            }
+        }
+        self.scroll_to_cursor();
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, _theme: &Theme) {
+        buffer.render_background(area, self.background);
+        let area = area.shrink(self.padding);
+        if area.is_empty() {
+            return;
+        }
+        let cursor_style = self.style.reverse();
+        let selection = self.selection();
+        let rows = self.rows();
+
+        for (y, row) in rows
+            .iter()
+            .skip(self.state.scroll)
+            .take(area.height as usize)
+            .enumerate()
+        {
+            let y = area.y + y as u16;
+            let mut x = 0;
+            for (index, grapheme) in self.value[row.start..row.end].grapheme_indices(true) {
+                let index = row.start + index;
+                let width = grapheme_width(grapheme);
+                if width == 0 {
+                    continue;
+                }
+                if x + width > area.width {
+                    break;
+                }
+                let style = if self.focused && index == self.state.cursor {
+                    cursor_style
+                } else if selection.is_some_and(|(start, end)| start <= index && index < end) {
+                    self.selection_style
+                } else {
+                    self.style
+                };
+                buffer.render_pixel(
+                    area.x + x,
+                    y,
+                    Pixel::from_graphemew(grapheme, width as u8).set_style(style),
+                );
+                x += width;
+            }
+            if self.focused && row.last && self.state.cursor == row.end && x < area.width {
+                buffer.render_pixel(area.x + x, y, Pixel::from_char(' ').set_style(cursor_style));
+            }
+        }
+    }
+
+    fn size(&self) -> Size {
+        let text = Text::new(&self.value);
+        Size::new(
+            text.size().width + 1 + self.padding.left + self.padding.right,
+            text.size().height.max(1) + self.padding.top + self.padding.bottom,
+        )
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn take_state(&mut self) -> Option<Box<dyn Any>> {
+        self.state.value = self.value.clone();
+        Some(Box::new(std::mem::take(&mut self.state)))
+    }
+
+    fn restore(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<State>() {
+            self.state = *state;
+            // The value has been changed by the program, so the history is no longer valid.
+            if self.state.value != self.value {
+                self.state.undo.clear();
+                self.state.redo.clear();
+                self.state.anchor = None;
+                self.state.cursor = self.state.cursor.min(self.value.len());
+                while !self.value.is_char_boundary(self.state.cursor) {
+                    self.state.cursor -= 1;
+                }
+            }
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<TextArea<Message>> for Element<'a, Message> {
+    fn from(text_area: TextArea<Message>) -> Self {
+        Self::new(text_area)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+
+    use crate::{geometry::area::Area, shell::Shell, widget::widget::Widget};
+
+    use super::{wrap, Row, TextArea};
+
+    fn press(area: &mut TextArea<String>, code: KeyCode, modifiers: KeyModifiers) -> Shell<String> {
+        let mut shell = Shell::new();
+        area.process_event(Event::Key(KeyEvent::new(code, modifiers)), &mut shell);
+        shell
+    }
+
+    #[test]
+    fn wrap_rows() {
+        assert_eq!(
+            wrap("ab你好\n", 3),
+            vec![
+                Row {
+                    start: 0,
+                    end: 2,
+                    last: false
+                },
+                Row {
+                    start: 2,
+                    end: 5,
+                    last: false
+                },
+                Row {
+                    start: 5,
+                    end: 8,
+                    last: true
+                },
+                Row {
+                    start: 9,
+                    end: 9,
+                    last: true
+                },
+            ]
+        );
+
+        // Lines are broken on words, the dropped spaces are kept in the row before.
+        let ranges = |value, width| {
+            wrap(value, width)
+                .into_iter()
+                .map(|row| (row.start, row.end, row.last))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ranges("the quick brown", 10),
+            vec![(0, 10, false), (10, 15, true)]
+        );
+        assert_eq!(ranges("abc d", 3), vec![(0, 4, false), (4, 5, true)]);
+        assert_eq!(ranges("abc ", 3), vec![(0, 4, false), (4, 4, true)]);
+    }
+
+    #[test]
+    fn edit_and_undo() {
+        let mut area = TextArea::new("ab\ncd").on_change(|value| value).padding(0);
+        area.set_focused(true);
+        area.layout(Area::new(0, 0, 10, 5));
+
+        press(&mut area, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut area, KeyCode::Left, KeyModifiers::SHIFT);
+        let shell = press(&mut area, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(shell.messages(), &vec!["ax\ncd".to_string()]);
+        press(&mut area, KeyCode::Char('y'), KeyModifiers::NONE);
+
+        let shell = press(&mut area, KeyCode::Char('z'), KeyModifiers::CONTROL);
+        assert_eq!(shell.messages(), &vec!["ab\ncd".to_string()]);
+        let shell = press(&mut area, KeyCode::Char('y'), KeyModifiers::CONTROL);
+        assert_eq!(shell.messages(), &vec!["axy\ncd".to_string()]);
+    }
+
+    #[test]
+    fn wheel_scroll() {
+        let value = (0..20)
+            .map(|i| format!("l{i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut area = TextArea::<String>::new(value).padding(0);
+        area.set_focused(true);
+        area.layout(Area::new(0, 0, 10, 4));
+        press(&mut area, KeyCode::End, KeyModifiers::NONE);
+        assert_eq!(area.state.scroll, 16);
+
+        let event = MouseEvent {
+            kind: MouseEventKind::ScrollUp,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+        area.process_event(Event::Mouse(event), &mut Shell::new());
+        assert_eq!(area.state.scroll, 13);
+        // The next frame keeps the scroll instead of going back to the cursor.
+        area.layout(Area::new(0, 0, 10, 4));
+        assert_eq!(area.state.scroll, 13);
+        // Only moving the cursor reveals it again.
+        press(&mut area, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(area.state.scroll, 16);
+    }
+}
@@ -0,0 +1,527 @@
+use std::any::Any;
+
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    buffer::{buffer::Buffer, pixel::Pixel},
+    geometry::{area::Area, length::Length, size::Size, spacing::Spacing},
+    shell::Shell,
+    style::{color::Color, style::Style, theme::Theme},
+    text::{grapheme_width, Text},
+    widget::{element::Element, widget::Widget},
+};
+
+/// The character displayed instead of each grapheme in password mode.
+const MASK: char = '•';
+
+/// The cursor and the horizontal scroll of a [`TextInput`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct State {
+    /// The byte index of the cursor in the value, always on a grapheme boundary.
+    cursor: usize,
+    /// The number of columns scrolled out on the left.
+    scroll: u16,
+}
+
+pub struct TextInput<Message: Clone> {
+    value: String,
+    placeholder: Text,
+    style: Style,
+    placeholder_style: Style,
+    padding: Spacing,
+    background: Option<Color>,
+    password: bool,
+    on_input: Option<Box<dyn Fn(String) -> Message>>,
+    on_submit: Option<Message>,
+    state: State,
+    focused: bool,
+    bounds: Area,
+    size_hint: Size<Length>,
+}
+
+pub fn text_input<Message: Clone>(
+    placeholder: impl Into<String>,
+    value: impl Into<String>,
+) -> TextInput<Message> {
+    TextInput::new(placeholder, value)
+}
+
+impl<Message: Clone> TextInput<Message> {
+    pub fn new(placeholder: impl Into<String>, value: impl Into<String>) -> Self {
+        let value = value.into();
+        Self {
+            state: State {
+                cursor: value.len(),
+                scroll: 0,
+            },
+            value,
+            placeholder: Text::new(placeholder),
+            style: Style::new().foreground(Color::Text),
+            placeholder_style: Style::new().foreground(Color::Text).dim(),
+            padding: Spacing::horizontal(1),
+            background: Some(Color::Background),
+            password: false,
+            on_input: None,
+            on_submit: None,
+            focused: false,
+            bounds: Area::zeros(),
+            size_hint: Size::new(Length::Fill, Length::Preferred),
+        }
+    }
+
+    /// Set the message produced with the new value when the value is edited.
+    /// The input is read-only without it.
+    pub fn on_input(mut self, on_input: impl Fn(String) -> Message + 'static) -> Self {
+        self.on_input = Some(Box::new(on_input));
+        self
+    }
+
+    /// Set the message produced when Enter is pressed.
+    pub fn on_submit(mut self, message: Message) -> Self {
+        self.on_submit = Some(message);
+        self
+    }
+
+    /// Display each grapheme of the value as a `•`.
+    pub fn password(mut self, password: bool) -> Self {
+        self.password = password;
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn placeholder_style(mut self, style: Style) -> Self {
+        self.placeholder_style = style;
+        self
+    }
+
+    pub fn padding(mut self, padding: impl Into<Spacing>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn width(mut self, width: Length) -> Self {
+        self.size_hint.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Length) -> Self {
+        self.size_hint.height = height;
+        self
+    }
+
+    /// The graphemes of the value as displayed, with their byte indices and widths.
+    fn graphemes(&self) -> impl Iterator<Item = (usize, &str, u16)> {
+        self.value
+            .grapheme_indices(true)
+            .map(move |(index, grapheme)| {
+                if self.password {
+                    (index, grapheme, 1)
+                } else {
+                    (index, grapheme, grapheme_width(grapheme))
+                }
+            })
+    }
+
+    /// The column of the cursor, relative to the start of the value.
+    fn cursor_column(&self) -> u16 {
+        self.graphemes()
+            .take_while(|(index, _, _)| *index < self.state.cursor)
+            .map(|(_, _, width)| width)
+            .sum()
+    }
+
+    /// The width of the cell under the cursor.
+    fn cursor_width(&self) -> u16 {
+        self.graphemes()
+            .find(|(index, _, _)| *index == self.state.cursor)
+            .map_or(1, |(_, _, width)| width.max(1))
+    }
+
+    /// Scroll horizontally, so that the cursor is visible.
+    fn scroll_to_cursor(&mut self) {
+        let width = self.bounds.shrink(self.padding).width;
+        let total: u16 = self.graphemes().map(|(_, _, width)| width).sum();
+        let column = self.cursor_column();
+        let cursor_width = self.cursor_width();
+        let scroll = &mut self.state.scroll;
+        *scroll = (*scroll).min((total + 1).saturating_sub(width));
+        if column < *scroll {
+            *scroll = column;
+        } else if column + cursor_width > *scroll + width {
+            *scroll = (column + cursor_width).saturating_sub(width);
+        }
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.value[..self.state.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.value[self.state.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.state.cursor, |grapheme| {
+                self.state.cursor + grapheme.len()
+            })
+    }
+
+    fn previous_word(&self) -> usize {
+        self.value[..self.state.cursor]
+            .split_word_bound_indices()
+            .rev()
+            .find(|(_, word)| !word.trim().is_empty())
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_word(&self) -> usize {
+        self.value[self.state.cursor..]
+            .split_word_bound_indices()
+            .find(|(_, word)| !word.trim().is_empty())
+            .map_or(self.value.len(), |(index, word)| {
+                self.state.cursor + index + word.len()
+            })
+    }
+
+    /// Insert a string at the cursor, ignoring control characters.
+    fn insert(&mut self, string: &str) -> bool {
+        let string: String = string.chars().filter(|char| !char.is_control()).collect();
+        if string.is_empty() {
+            return false;
+        }
+        self.value.insert_str(self.state.cursor, &string);
+        self.state.cursor += string.len();
+        true
+    }
+
+    /// Remove the bytes between the cursor and `index`, and move the cursor to the start.
+    fn remove_to(&mut self, index: usize) -> bool {
+        let (start, end) = if index < self.state.cursor {
+            (index, self.state.cursor)
+        } else {
+            (self.state.cursor, index)
+        };
+        if start == end {
+            return false;
+        }
+        self.value.replace_range(start..end, "");
+        self.state.cursor = start;
+        true
+    }
+
+    /// Process a key press, returns whether the value has been changed.
+    fn process_key(&mut self, event: KeyEvent, shell: &mut Shell<Message>) -> bool {
+        let editable = self.on_input.is_some();
+        let word = event.modifiers.contains(KeyModifiers::CONTROL);
+        match event.code {
+            KeyCode::Char(char)
+                if editable
+                    && !event
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.insert(char.encode_utf8(&mut [0; 4]))
+            }
+            KeyCode::Backspace if editable => {
+                let index = if word {
+                    self.previous_word()
+                } else {
+                    self.previous_boundary()
+                };
+                self.remove_to(index)
+            }
+            KeyCode::Delete if editable => {
+                let index = if word {
+                    self.next_word()
+                } else {
+                    self.next_boundary()
+                };
+                self.remove_to(index)
+            }
+            KeyCode::Left => {
+                self.state.cursor = if word {
+                    self.previous_word()
+                } else {
+                    self.previous_boundary()
+                };
+                false
+            }
+            KeyCode::Right => {
+                self.state.cursor = if word {
+                    self.next_word()
+                } else {
+                    self.next_boundary()
+                };
+                false
+            }
+            KeyCode::Home => {
+                self.state.cursor = 0;
+                false
+            }
+            KeyCode::End => {
+                self.state.cursor = self.value.len();
+                false
+            }
+            KeyCode::Enter => {
+                if let Some(message) = self.on_submit.clone() {
+                    shell.push(message);
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Move the cursor to the grapheme displayed at a column of the screen.
+    fn move_cursor_to(&mut self, column: u16) {
+        let column = (column + self.state.scroll).saturating_sub(self.bounds.x + self.padding.left);
+        let mut start = 0;
+        let cursor = self
+            .graphemes()
+            .find(|(_, _, width)| {
+                start += width;
+                column < start
+            })
+            .map_or(self.value.len(), |(index, _, _)| index);
+        self.state.cursor = cursor;
+    }
+}
+
+impl<Message: Clone> Widget<Message> for TextInput<Message> {
+    fn layout(&mut self, viewport: Area) {
+        self.bounds = viewport;
+        self.scroll_to_cursor();
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() {
+            return;
+        }
+        let changed = match event {
+            Event::Key(event) if self.focused => {
+                if matches!(event.code, KeyCode::Tab | KeyCode::BackTab | KeyCode::Esc) {
+                    return;
+                }
+                shell.capture_event();
+                if event.kind == KeyEventKind::Release {
+                    return;
+                }
+                self.process_key(event, shell)
+            }
+            Event::Paste(string) if self.focused && self.on_input.is_some() => {
+                shell.capture_event();
+                self.insert(&string)
+            }
+            Event::Mouse(event)
+                if event.kind == MouseEventKind::Down(MouseButton::Left)
+                    && self.bounds.contains(event.column, event.row) =>
+            {
+                shell.capture_event();
+                if !self.focused {
+                    self.focused = true;
+                    shell.request_focus();
+                }
+                self.move_cursor_to(event.column);
+                false
+            }
+            _ => return,
+        };
+        if changed {
+            if let Some(on_input) = &self.on_input {
+                shell.push(on_input(self.value.clone()));
+            }
+        }
+        self.scroll_to_cursor();
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, _theme: &Theme) {
+        buffer.render_background(area, self.background);
+        let area = area.shrink(self.padding);
+        if area.is_empty() {
+            return;
+        }
+        let cursor_style = self.style.reverse();
+        if self.value.is_empty() {
+            buffer.render_string(self.placeholder.raw(), self.placeholder_style, area, false);
+            if self.focused && buffer.area().contains(area.x, area.y) {
+                let pixel = buffer.get(area.x, area.y).clone();
+                let mut pixel = match pixel.grapheme() {
+                    Some(grapheme) => Pixel::from_graphemew(grapheme, pixel.width()),
+                    None => Pixel::from_char(' '),
+                };
+                buffer.render_pixel(area.x, area.y, pixel.set_style(cursor_style));
+            }
+            return;
+        }
+
+        let scroll = self.state.scroll;
+        let mut column = 0;
+        for (index, grapheme, width) in self.graphemes() {
+            let start = column;
+            column += width;
+            if width == 0 {
+                continue;
+            }
+            let style = if self.focused && index == self.state.cursor {
+                cursor_style
+            } else {
+                self.style
+            };
+            if start < scroll {
+                // A two-width character partially scrolled out.
+                if column > scroll {
+                    buffer.render_pixel(area.x, area.y, Pixel::from_char(' ').set_style(style));
+                }
+                continue;
+            }
+            let x = start - scroll;
+            if x + width > area.width {
+                if x < area.width {
+                    buffer.render_pixel(area.x + x, area.y, Pixel::from_char(' ').set_style(style));
+                }
+                break;
+            }
+            let mut pixel = if self.password {
+                Pixel::from_charw(MASK, 1)
+            } else {
+                Pixel::from_graphemew(grapheme, width as u8)
+            };
+            buffer.render_pixel(area.x + x, area.y, pixel.set_style(style));
+        }
+        if self.focused && self.state.cursor == self.value.len() && column >= scroll {
+            let x = column - scroll;
+            if x < area.width {
+                buffer.render_pixel(
+                    area.x + x,
+                    area.y,
+                    Pixel::from_char(' ').set_style(cursor_style),
+                );
+            }
+        }
+    }
+
+    fn size(&self) -> Size {
+        let width: u16 = self.graphemes().map(|(_, _, width)| width).sum();
+        Size::new(
+            width.max(self.placeholder.size().width) + 1 + self.padding.left + self.padding.right,
+            1 + self.padding.top + self.padding.bottom,
+        )
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn take_state(&mut self) -> Option<Box<dyn Any>> {
+        Some(Box::new(self.state))
+    }
+
+    fn restore(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<State>() {
+            self.state = *state;
+            // The value may have been changed by the program.
+            self.state.cursor = self.state.cursor.min(self.value.len());
+            while !self.value.is_char_boundary(self.state.cursor) {
+                self.state.cursor -= 1;
+            }
+        }
+    }
+}
+
+impl<'a, Message: Clone + 'a> From<TextInput<Message>> for Element<'a, Message> {
+    fn from(text_input: TextInput<Message>) -> Self {
+        Self::new(text_input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::{
+        buffer::buffer::Buffer, geometry::area::Area, shell::Shell, style::theme::Theme,
+        widget::widget::Widget,
+    };
+
+    use super::TextInput;
+
+    fn press(
+        input: &mut TextInput<String>,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Shell<String> {
+        let mut shell = Shell::new();
+        input.process_event(Event::Key(KeyEvent::new(code, modifiers)), &mut shell);
+        shell
+    }
+
+    fn line(buffer: &Buffer) -> String {
+        (0..buffer.width())
+            .filter_map(|x| buffer.get(x, 0).grapheme())
+            .collect()
+    }
+
+    #[test]
+    fn edit() {
+        let mut input = TextInput::new("", "e\u{301}你好").on_input(|value| value);
+        input.set_focused(true);
+        input.layout(Area::new(0, 0, 20, 1));
+
+        press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        let shell = press(&mut input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(shell.messages(), &vec!["e\u{301}好".to_string()]);
+        assert!(shell.is_event_captured());
+
+        press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Delete, KeyModifiers::NONE);
+        let shell = press(&mut input, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(shell.messages(), &vec!["x好".to_string()]);
+    }
+
+    #[test]
+    fn scroll() {
+        let mut input: TextInput<String> = TextInput::new("", "你好，世界！").padding(0);
+        input.set_focused(true);
+        input.layout(Area::new(0, 0, 5, 1));
+        let mut buffer = Buffer::new(5, 1);
+        input.render(Area::new(0, 0, 5, 1), &mut buffer, &Theme::TOKYO_NIGHT);
+        assert_eq!(line(&buffer), "界！ ");
+
+        press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        let mut buffer = Buffer::new(5, 1);
+        input.render(Area::new(0, 0, 5, 1), &mut buffer, &Theme::TOKYO_NIGHT);
+        assert_eq!(line(&buffer), "你好 ");
+
+        let mut password: TextInput<String> = TextInput::new("", "你好").password(true);
+        let mut buffer = Buffer::new(6, 1);
+        password.layout(Area::new(0, 0, 6, 1));
+        password.render(Area::new(0, 0, 6, 1), &mut buffer, &Theme::TOKYO_NIGHT);
+        assert_eq!(line(&buffer), "••");
+    }
+}
@@ -0,0 +1,130 @@
+use crossterm::event::Event;
+
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::{area::Area, length::Length, size::Size},
+    shell::Shell,
+    style::{color::Color, style::Style, theme::Theme},
+    text::StyledText,
+    widget::{element::Element, widget::Widget},
+    widgets::checkbox::Checkbox,
+};
+
+/// A switch turned on and off with Space or a click, followed by a label. It behaves
+/// like a [`Checkbox`] drawn with a switch instead of a box.
+pub struct Toggle<Message>(Checkbox<Message>);
+
+pub fn toggle<Message>(label: impl Into<StyledText>, on: bool) -> Toggle<Message> {
+    Toggle::new(label, on)
+}
+
+fn switch_glyph(on: bool, ascii: bool) -> &'static str {
+    match (on, ascii) {
+        (true, false) => "━━●",
+        (false, false) => "●━━",
+        (true, true) => "--o",
+        (false, true) => "o--",
+    }
+}
+
+impl<Message> Toggle<Message> {
+    pub fn new(label: impl Into<StyledText>, on: bool) -> Self {
+        Self(Checkbox::new(label, on).indicator(switch_glyph, (Color::Success, Color::Disabled)))
+    }
+
+    /// Set the message produced with the new state when the user flips the switch.
+    pub fn on_toggle(self, on_toggle: impl Fn(bool) -> Message + 'static) -> Self {
+        Self(self.0.on_toggle(on_toggle))
+    }
+
+    /// Set the base style of the label.
+    pub fn style(self, style: Style) -> Self {
+        Self(self.0.style(style))
+    }
+
+    pub fn background(self, background: Option<Color>) -> Self {
+        Self(self.0.background(background))
+    }
+
+    pub fn width(self, width: impl Into<Length>) -> Self {
+        Self(self.0.width(width))
+    }
+
+    pub fn height(self, height: impl Into<Length>) -> Self {
+        Self(self.0.height(height))
+    }
+}
+
+impl<Message> Widget<Message> for Toggle<Message> {
+    fn layout(&mut self, viewport: Area) {
+        self.0.layout(viewport);
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        self.0.process_event(event, shell);
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, theme: &Theme) {
+        self.0.render(area, buffer, theme);
+    }
+
+    fn size(&self) -> Size {
+        self.0.size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.0.size_hint()
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.0.set_focused(focused);
+    }
+
+    fn is_focused(&self) -> bool {
+        self.0.is_focused()
+    }
+}
+
+impl<'a, Message: 'a> From<Toggle<Message>> for Element<'a, Message> {
+    fn from(value: Toggle<Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::{
+        buffer::buffer::Buffer,
+        geometry::area::Area,
+        shell::Shell,
+        style::{color::ColorSystem, theme::Theme},
+        widget::widget::Widget,
+    };
+
+    use super::toggle;
+
+    #[test]
+    fn switch() {
+        let mut toggle = toggle("Wi-Fi", false).on_toggle(|on| on);
+        let area = Area::from_wh(9, 1);
+        toggle.layout(area);
+        let mut buffer = Buffer::new(9, 1);
+        toggle.render(area, &mut buffer, &Theme::TOKYO_NIGHT);
+        assert_eq!(buffer.text(), "●━━ Wi-Fi");
+        buffer.set_color_system(ColorSystem::LegacyWindows);
+        toggle.render(area, &mut buffer, &Theme::TOKYO_NIGHT);
+        assert_eq!(buffer.text(), "o-- Wi-Fi");
+
+        toggle.set_focused(true);
+        let mut shell = Shell::new();
+        let space = KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE);
+        toggle.process_event(Event::Key(space), &mut shell);
+        assert_eq!(shell.messages(), &vec![true]);
+    }
+}
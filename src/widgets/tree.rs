@@ -0,0 +1,669 @@
+use std::{
+    any::Any,
+    collections::HashSet,
+    hash::{DefaultHasher, Hasher},
+};
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind};
+
+use crate::{
+    buffer::buffer::Buffer,
+    geometry::{area::Area, length::Length, size::Size},
+    shell::Shell,
+    style::{color::Color, style::Style, theme::Theme},
+    text::{layout::TextLayout, Line, StyledText},
+    widget::{element::Element, widget::Widget},
+    widgets::scroll::{self, WHEEL_STEP},
+};
+
+/// The number of columns of an indentation level, and of the expander.
+const INDENT: u16 = 2;
+
+/// The scroll, the highlighted node and the nodes toggled by the user in a [`Tree`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct State {
+    /// The index of the first visible row.
+    offset: usize,
+    /// The identifier of the highlighted node, moved with the keyboard. Starts on the
+    /// selected node.
+    cursor: Option<u64>,
+    /// The identifiers of the nodes expanded or collapsed by the user, from their
+    /// initial state, see [`TreeNode::expanded`].
+    toggled: HashSet<u64>,
+}
+
+/// A node of a [`Tree`], with its children.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeNode {
+    label: StyledText,
+    key: Option<String>,
+    children: Vec<TreeNode>,
+    expanded: bool,
+    lazy: bool,
+}
+
+impl TreeNode {
+    pub fn new(label: impl Into<StyledText>) -> Self {
+        Self {
+            label: label.into(),
+            key: None,
+            children: Vec::new(),
+            expanded: false,
+            lazy: false,
+        }
+    }
+
+    /// Set the key identifying the node among its siblings, which defaults to the text
+    /// of the label. The tree keeps the expanded and the highlighted nodes by their keys,
+    /// so that they stay the same when the program inserts or removes nodes: give
+    /// different keys to siblings with the same label.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Add a child at the end of the children.
+    pub fn child(mut self, child: TreeNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn children(mut self, children: impl IntoIterator<Item = TreeNode>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// Set whether the node is initially expanded. The user can toggle it afterwards.
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+
+    /// Mark the node as having children not loaded yet. The node can be expanded, and
+    /// the program should load its children on the message of [`Tree::on_expand`].
+    pub fn lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
+    fn has_children(&self) -> bool {
+        self.lazy || !self.children.is_empty()
+    }
+
+    /// Get the identifier of the node from the one of its parent and its key.
+    fn id(&self, parent: u64) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_u64(parent);
+        match &self.key {
+            Some(key) => hasher.write(key.as_bytes()),
+            None => {
+                for line in &self.label.lines {
+                    for span in &line.spans {
+                        hasher.write(span.content.as_bytes());
+                    }
+                    hasher.write_u8(b'\n');
+                }
+            }
+        }
+        hasher.finish()
+    }
+}
+
+/// A visible node of a tree, in the order of the rows.
+#[derive(Debug, Clone, Copy)]
+struct Row {
+    /// The identifier of the node, see [`TreeNode::key`].
+    id: u64,
+    /// The index of the node among its siblings, and the row of its parent.
+    index: usize,
+    parent: Option<usize>,
+    depth: u16,
+    /// Whether the node is the last of its siblings.
+    last: bool,
+    expanded: bool,
+    /// Whether the node can be expanded, and whether its children are loaded.
+    expandable: bool,
+    loaded: bool,
+    /// The width of the indentation, the expander and the first line of the label.
+    width: u16,
+}
+
+/// Hierarchical nodes drawn with indentation guides, which can be expanded and
+/// collapsed with the arrow keys or by clicking their expander.
+///
+/// The program identifies the nodes by their paths, i.e. the indices of the nodes from
+/// the root list to the node.
+pub struct Tree<Message> {
+    roots: Vec<TreeNode>,
+    selected: Option<Vec<usize>>,
+    on_select: Option<Box<dyn Fn(Vec<usize>) -> Message>>,
+    on_expand: Option<Box<dyn Fn(Vec<usize>) -> Message>>,
+    style: Style,
+    highlight_style: Style,
+    selected_style: Style,
+    background: Option<Color>,
+    bounds: Area,
+    /// The visible nodes, built again when the expanded nodes change.
+    rows: Vec<Row>,
+    state: State,
+    focused: bool,
+    size_hint: Size<Length>,
+}
+
+pub fn tree<Message>(roots: impl IntoIterator<Item = TreeNode>) -> Tree<Message> {
+    Tree::new(roots)
+}
+
+impl<Message> Tree<Message> {
+    pub fn new(roots: impl IntoIterator<Item = TreeNode>) -> Self {
+        let mut tree = Self {
+            roots: roots.into_iter().collect(),
+            selected: None,
+            on_select: None,
+            on_expand: None,
+            style: Style::new().foreground(Color::Text),
+            highlight_style: Style::new().background(Color::Selection),
+            selected_style: Style::new().foreground(Color::Primary).bold(),
+            background: None,
+            bounds: Area::zeros(),
+            rows: Vec::new(),
+            state: State::default(),
+            focused: false,
+            size_hint: Size::new(Length::Fill, Length::Fill),
+        };
+        tree.build_rows();
+        tree
+    }
+
+    /// Set the path of the selected node.
+    pub fn selected(mut self, selected: Option<Vec<usize>>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Set the message produced with the path of a node chosen with Enter, Space or a
+    /// click.
+    pub fn on_select(mut self, on_select: impl Fn(Vec<usize>) -> Message + 'static) -> Self {
+        self.on_select = Some(Box::new(on_select));
+        self
+    }
+
+    /// Set the message produced with the path of an expanded node, e.g. to load the
+    /// children of a lazy node.
+    pub fn on_expand(mut self, on_expand: impl Fn(Vec<usize>) -> Message + 'static) -> Self {
+        self.on_expand = Some(Box::new(on_expand));
+        self
+    }
+
+    /// Set the base style of the labels.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style patched on the highlighted node.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Set the style patched on the selected node.
+    pub fn selected_style(mut self, style: Style) -> Self {
+        self.selected_style = style;
+        self
+    }
+
+    pub fn background(mut self, background: Option<Color>) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.size_hint.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.size_hint.height = height.into();
+        self
+    }
+
+    /// Build the visible nodes, i.e. the roots and the descendants of expanded nodes.
+    fn build_rows(&mut self) {
+        fn visit(
+            nodes: &[TreeNode],
+            parent: Option<(usize, u64)>,
+            depth: u16,
+            toggled: &HashSet<u64>,
+            rows: &mut Vec<Row>,
+        ) {
+            for (i, node) in nodes.iter().enumerate() {
+                let id = node.id(parent.map_or(0, |(_, id)| id));
+                let expanded = node.has_children() && node.expanded != toggled.contains(&id);
+                let label = node.label.lines.first().map_or(0, Line::width);
+                rows.push(Row {
+                    id,
+                    index: i,
+                    parent: parent.map(|(row, _)| row),
+                    depth,
+                    last: i + 1 == nodes.len(),
+                    expanded,
+                    expandable: node.has_children(),
+                    loaded: !node.children.is_empty(),
+                    width: (depth + 1) * INDENT + label,
+                });
+                if expanded {
+                    let parent = Some((rows.len() - 1, id));
+                    visit(&node.children, parent, depth + 1, toggled, rows);
+                }
+            }
+        }
+        let mut rows = std::mem::take(&mut self.rows);
+        rows.clear();
+        visit(&self.roots, None, 0, &self.state.toggled, &mut rows);
+        self.rows = rows;
+    }
+
+    /// Get the path of the node of a row.
+    fn path(&self, row: usize) -> Vec<usize> {
+        let mut path = Vec::with_capacity(self.rows[row].depth as usize + 1);
+        let mut row = Some(row);
+        while let Some(current) = row {
+            path.push(self.rows[current].index);
+            row = self.rows[current].parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Get the row of the node at a path, if it is visible.
+    fn find(&self, path: &[usize]) -> Option<usize> {
+        let (&first, rest) = path.split_first()?;
+        let mut row = self
+            .rows
+            .iter()
+            .position(|row| row.depth == 0 && row.index == first)?;
+        for &index in rest {
+            // The children of an expanded node follow it, with their descendants.
+            let depth = self.rows[row].depth + 1;
+            row = (row + 1..self.rows.len())
+                .take_while(|&child| self.rows[child].depth >= depth)
+                .find(|&child| {
+                    self.rows[child].depth == depth && self.rows[child].index == index
+                })?;
+        }
+        Some(row)
+    }
+
+    /// Get the node of a row.
+    fn node(&self, row: usize) -> &TreeNode {
+        let path = self.path(row);
+        let mut node = &self.roots[path[0]];
+        for &index in &path[1..] {
+            node = &node.children[index];
+        }
+        node
+    }
+
+    /// Get the row of the highlighted node, if there are nodes.
+    fn cursor(&self) -> Option<usize> {
+        let last = self.rows.len().checked_sub(1)?;
+        let row = match (self.state.cursor, &self.selected) {
+            (Some(id), _) => self.rows.iter().position(|row| row.id == id),
+            (None, Some(path)) => self.find(path),
+            (None, None) => None,
+        };
+        Some(row.unwrap_or(0).min(last))
+    }
+
+    fn scroll_to(&mut self, offset: usize) {
+        let visible = self.bounds.height as usize;
+        self.state.offset = scroll::clamp(offset, self.rows.len(), visible);
+    }
+
+    /// Highlight the node of a row and scroll to show it.
+    fn move_cursor(&mut self, row: usize) {
+        let visible = self.bounds.height as usize;
+        self.state.cursor = Some(self.rows[row].id);
+        self.state.offset = scroll::reveal(self.state.offset, row, self.rows.len(), visible);
+    }
+
+    /// Expand or collapse the node of a row.
+    fn toggle(&mut self, row: usize, shell: &mut Shell<Message>) {
+        let Row { id, expanded, .. } = self.rows[row];
+        if !self.state.toggled.remove(&id) {
+            self.state.toggled.insert(id);
+        }
+        if !expanded {
+            if let Some(on_expand) = &self.on_expand {
+                shell.push(on_expand(self.path(row)));
+            }
+        }
+        self.build_rows();
+    }
+
+    fn select(&self, row: usize, shell: &mut Shell<Message>) {
+        if let Some(on_select) = &self.on_select {
+            shell.push(on_select(self.path(row)));
+        }
+    }
+
+    /// Move the cursor, expand, collapse or select with a key. Returns whether the key
+    /// has been handled.
+    fn process_key(&mut self, code: KeyCode, shell: &mut Shell<Message>) -> bool {
+        let Some(cursor) = self.cursor() else {
+            return false;
+        };
+        let last = self.rows.len() - 1;
+        let page = (self.bounds.height as usize).max(1);
+        let row = self.rows[cursor];
+        let target = match code {
+            KeyCode::Up => cursor.saturating_sub(1),
+            KeyCode::Down => (cursor + 1).min(last),
+            KeyCode::PageUp => cursor.saturating_sub(page),
+            KeyCode::PageDown => (cursor + page).min(last),
+            KeyCode::Home => 0,
+            KeyCode::End => last,
+            // Expand the node, or go to its first child.
+            KeyCode::Right if row.expandable && !row.expanded => {
+                self.toggle(cursor, shell);
+                return true;
+            }
+            KeyCode::Right if row.expanded && row.loaded => cursor + 1,
+            // Collapse the node, or go to its parent.
+            KeyCode::Left if row.expanded => {
+                self.toggle(cursor, shell);
+                return true;
+            }
+            KeyCode::Left => match row.parent {
+                Some(parent) => parent,
+                None => return true,
+            },
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                self.select(cursor, shell);
+                return true;
+            }
+            _ => return false,
+        };
+        self.move_cursor(target);
+        true
+    }
+
+    fn process_click(&mut self, column: u16, row: u16, shell: &mut Shell<Message>) {
+        let index = self.state.offset + (row - self.bounds.y) as usize;
+        let Some(row) = self.rows.get(index) else {
+            return;
+        };
+        let expander = self.bounds.x + row.depth * INDENT;
+        if row.expandable && (expander..expander + INDENT).contains(&column) {
+            self.toggle(index, shell);
+        } else {
+            self.move_cursor(index);
+            self.select(index, shell);
+        }
+    }
+
+    /// Get the indentation guides of a row, drawn from the ancestors below the roots.
+    fn guides(&self, row: usize) -> String {
+        let mut guides = Vec::with_capacity(self.rows[row].depth as usize);
+        let mut current = self.rows[row];
+        let mut connector = true;
+        while let Some(parent) = current.parent {
+            guides.push(match (connector, current.last) {
+                (true, true) => "└─",
+                (true, false) => "├─",
+                (false, true) => "  ",
+                (false, false) => "│ ",
+            });
+            connector = false;
+            current = self.rows[parent];
+        }
+        guides.reverse();
+        guides.concat()
+    }
+}
+
+impl<Message> Widget<Message> for Tree<Message> {
+    fn layout(&mut self, viewport: Area) {
+        self.bounds = viewport;
+        self.scroll_to(self.state.offset);
+    }
+
+    fn process_event(&mut self, event: Event, shell: &mut Shell<Message>) {
+        if shell.is_event_captured() {
+            return;
+        }
+        match event {
+            Event::Key(event) if self.focused && event.kind != KeyEventKind::Release => {
+                let handled = self.process_key(event.code, shell);
+                if handled {
+                    shell.capture_event();
+                }
+            }
+            Event::Mouse(event) if self.bounds.contains(event.column, event.row) => {
+                match event.kind {
+                    MouseEventKind::ScrollUp => {
+                        self.scroll_to(self.state.offset.saturating_sub(WHEEL_STEP))
+                    }
+                    MouseEventKind::ScrollDown => {
+                        self.scroll_to(self.state.offset.saturating_add(WHEEL_STEP))
+                    }
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        if !self.focused {
+                            self.focused = true;
+                            shell.request_focus();
+                        }
+                        self.process_click(event.column, event.row, shell);
+                    }
+                    _ => return,
+                }
+                shell.capture_event();
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&self, area: Area, buffer: &mut Buffer, _theme: &Theme) {
+        buffer.render_background(area, self.background);
+        if area.is_empty() {
+            return;
+        }
+        let cursor = self.cursor();
+        let guide_style = Style::new().foreground(Color::Border);
+        let layout = TextLayout::new().wrap(false).truncate(true);
+        let visible = self.rows.iter().enumerate().skip(self.state.offset);
+        for (y, (index, row)) in (area.y..area.y + area.height).zip(visible) {
+            let expander = match (row.expandable, row.expanded) {
+                (true, true) => "▾ ",
+                (true, false) => "▸ ",
+                (false, _) => "  ",
+            };
+
+            let path = self.path(index);
+            let mut style = self.style;
+            if self.selected.as_ref() == Some(&path) {
+                style = style.patch(self.selected_style);
+            }
+            if cursor == Some(index) {
+                style = style.patch(self.highlight_style);
+                if self.focused {
+                    style = style.foreground(Color::Focus);
+                }
+            }
+            let indent = row.depth * INDENT;
+            let label_x = indent + INDENT;
+            let row_area = Area::new(area.x, y, area.width, 1);
+            buffer.render_background(row_area, style.background);
+            buffer.render_string(self.guides(index), guide_style, row_area, false);
+            buffer.render_string(
+                expander,
+                style,
+                Area::new(area.x + indent, y, area.width.saturating_sub(indent), 1),
+                false,
+            );
+            let line = StyledText::new(self.node(index).label.lines.first().cloned());
+            let label_area = Area::new(
+                area.x.saturating_add(label_x),
+                y,
+                area.width.saturating_sub(label_x),
+                1,
+            );
+            buffer.render_text(&line, style, label_area, &layout);
+        }
+    }
+
+    fn size(&self) -> Size {
+        let width = self.rows.iter().map(|row| row.width).max().unwrap_or(0);
+        let height = self.rows.len().min(u16::MAX as usize) as u16;
+        Size::new(width, height)
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.size_hint
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn take_state(&mut self) -> Option<Box<dyn Any>> {
+        Some(Box::new(std::mem::take(&mut self.state)))
+    }
+
+    fn restore(&mut self, state: Box<dyn Any>) {
+        if let Ok(state) = state.downcast::<State>() {
+            self.state = *state;
+            self.build_rows();
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<Tree<Message>> for Element<'a, Message> {
+    fn from(value: Tree<Message>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    use crate::{
+        buffer::buffer::Buffer, geometry::area::Area, shell::Shell, style::theme::Theme,
+        widget::widget::Widget, widgets::testing::press,
+    };
+
+    use super::{tree, Tree, TreeNode};
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum Message {
+        Select(Vec<usize>),
+        Expand(Vec<usize>),
+    }
+
+    fn project() -> Tree<Message> {
+        tree([
+            TreeNode::new("src").expanded(true).children([
+                TreeNode::new("widgets").child(TreeNode::new("tree.rs")),
+                TreeNode::new("text")
+                    .expanded(true)
+                    .child(TreeNode::new("layout.rs")),
+                TreeNode::new("lib.rs"),
+            ]),
+            TreeNode::new("target").lazy(true),
+        ])
+        .on_select(Message::Select)
+        .on_expand(Message::Expand)
+    }
+
+    fn render(tree: &mut Tree<Message>) -> String {
+        let mut buffer = Buffer::new(16, 8);
+        tree.layout(Area::from_wh(16, 8));
+        tree.render(Area::from_wh(16, 8), &mut buffer, &Theme::TOKYO_NIGHT);
+        buffer.text()
+    }
+
+    #[test]
+    fn guides() {
+        assert_eq!(
+            render(&mut project()),
+            [
+                "▾ src",
+                "├─▸ widgets",
+                "├─▾ text",
+                "│ └─  layout.rs",
+                "└─  lib.rs",
+                "▸ target",
+                "",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn navigation() {
+        let mut tree = project();
+        tree.layout(Area::from_wh(16, 8));
+        tree.set_focused(true);
+
+        // Expand the first child, go into it and select its child.
+        let keys = [
+            KeyCode::Down,
+            KeyCode::Right,
+            KeyCode::Right,
+            KeyCode::Enter,
+        ];
+        assert_eq!(
+            press(&mut tree, &keys),
+            vec![Message::Expand(vec![0, 0]), Message::Select(vec![0, 0, 0])]
+        );
+        // Go back to the parent and collapse it.
+        assert!(press(&mut tree, &[KeyCode::Left, KeyCode::Left]).is_empty());
+        assert!(render(&mut tree).starts_with("▾ src\n├─▸ widgets\n├─▾ text\n"));
+
+        // Expand the lazy node with a click on its expander.
+        let mut shell = Shell::new();
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 0,
+            row: 5,
+            modifiers: KeyModifiers::NONE,
+        };
+        tree.process_event(Event::Mouse(click), &mut shell);
+        assert_eq!(shell.messages(), &vec![Message::Expand(vec![1])]);
+        assert!(render(&mut tree).contains("▾ target"));
+    }
+
+    #[test]
+    fn keys() {
+        let folders = |names: &[&str]| {
+            tree(names.iter().map(|name| TreeNode::new(*name).lazy(true)))
+                .on_select(Message::Select)
+        };
+        let mut tree = folders(&["b", "c"]);
+        tree.layout(Area::from_wh(16, 8));
+        tree.set_focused(true);
+        press(&mut tree, &[KeyCode::Down, KeyCode::Right]);
+
+        // The expanded and the highlighted nodes follow their keys when a node is
+        // inserted before them.
+        let state = tree.take_state().unwrap();
+        let mut tree = folders(&["a", "b", "c"]);
+        tree.restore(state);
+        tree.set_focused(true);
+        assert!(render(&mut tree).starts_with("▸ a\n▸ b\n▾ c\n"));
+        assert_eq!(
+            press(&mut tree, &[KeyCode::Enter]),
+            vec![Message::Select(vec![2])]
+        );
+    }
+}
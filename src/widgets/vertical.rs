@@ -33,6 +33,12 @@ macro_rules! vertical {
     };
 }
 
+impl<Message> Default for Vertical<'_, Message> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a, Message> Vertical<'a, Message> {
     pub fn new() -> Self {
         Self {
@@ -88,19 +94,23 @@ impl<'a, Message> Vertical<'a, Message> {
         self.spacing = spacing;
         self
     }
-}
 
-impl<'a, Message> Widget<Message> for Vertical<'a, Message> {
-    fn layout(&mut self, viewport: Area) {
-        let children_widths: Vec<_> = self
-            .children
+    /// Get the widths of the children laid out in `width` columns.
+    fn children_widths(&self, width: u16) -> Vec<u16> {
+        self.children
             .iter()
             .map(|child| match child.widget().size_hint().width {
-                Length::Preferred => child.widget().size().width.min(viewport.width),
-                Length::Fixed(width) => width.min(viewport.width),
-                _ => viewport.width,
+                Length::Preferred => child.widget().size().width.min(width),
+                Length::Fixed(fixed) => fixed.min(width),
+                _ => width,
             })
-            .collect();
+            .collect()
+    }
+}
+
+impl<'a, Message> Widget<Message> for Vertical<'a, Message> {
+    fn layout(&mut self, viewport: Area) {
+        let children_widths = self.children_widths(viewport.width);
         let children_heights = Length::resolve(
             viewport.height,
             self.children
@@ -109,14 +119,15 @@ impl<'a, Message> Widget<Message> for Vertical<'a, Message> {
                 .collect(),
             self.children
                 .iter()
-                .map(|child| child.widget().size().height)
+                .zip(&children_widths)
+                .map(|(child, &width)| child.widget().height_for_width(width))
                 .collect(),
         );
 
         let mut y = viewport.y;
         for (i, (width, height)) in children_widths
             .into_iter()
-            .zip(children_heights.into_iter())
+            .zip(children_heights)
             .enumerate()
         {
             let x = match self.alignment {
@@ -145,11 +156,10 @@ impl<'a, Message> Widget<Message> for Vertical<'a, Message> {
     }
 
     fn process_event(&mut self, event: crossterm::event::Event, shell: &mut Shell<Message>) {
-        if shell.is_event_captured() {
-            return;
-        }
-
-        for child in &mut self.children.iter_mut().rev() {
+        for child in self.children.iter_mut().rev() {
+            if shell.is_event_captured() {
+                return;
+            }
             child.widget_mut().process_event(event.clone(), shell);
         }
     }
@@ -168,9 +178,26 @@ impl<'a, Message> Widget<Message> for Vertical<'a, Message> {
         )
     }
 
+    fn height_for_width(&self, width: u16) -> u16 {
+        let spacing = self.spacing * (self.children.len() as u16).saturating_sub(1);
+        self.children
+            .iter()
+            .zip(self.children_widths(width))
+            .map(|(child, width)| child.widget().height_for_width(width))
+            .sum::<u16>()
+            + spacing
+    }
+
     fn size_hint(&self) -> Size<Length> {
         self.size_hint
     }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget<Message>> {
+        self.children
+            .iter_mut()
+            .map(|child| child.widget_mut())
+            .collect()
+    }
 }
 
 impl<'a, Message: 'a> From<Vertical<'a, Message>> for Element<'a, Message> {
@@ -178,3 +205,95 @@ impl<'a, Message: 'a> From<Vertical<'a, Message>> for Element<'a, Message> {
         Self::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    use crate::{
+        buffer::buffer::Buffer,
+        geometry::{area::Area, size::Size},
+        shell::Shell,
+        style::theme::Theme,
+        widget::{element::Element, widget::Widget},
+        widgets::button::button,
+    };
+
+    use super::Vertical;
+
+    /// Counts the events it is given and captures them if `capture` is set.
+    struct Probe {
+        events: Rc<Cell<usize>>,
+        capture: bool,
+    }
+
+    impl Widget<i32> for Probe {
+        fn render(&self, _area: Area, _buffer: &mut Buffer, _theme: &Theme) {}
+
+        fn process_event(&mut self, _event: Event, shell: &mut Shell<i32>) {
+            if shell.is_event_captured() {
+                return;
+            }
+            self.events.set(self.events.get() + 1);
+            if self.capture {
+                shell.capture_event();
+            }
+        }
+
+        fn size(&self) -> Size {
+            Size::new(4, 1)
+        }
+
+        fn layout(&mut self, _viewport: Area) {}
+    }
+
+    fn click(column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn capture() {
+        let (top, bottom) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        // The last child is on top, so it is given the events first.
+        let mut column = Vertical::with_children(vec![
+            Element::new(Probe {
+                events: bottom.clone(),
+                capture: false,
+            }),
+            Element::new(Probe {
+                events: top.clone(),
+                capture: true,
+            }),
+        ]);
+        let mut shell = Shell::new();
+        column.process_event(click(0, 0), &mut shell);
+        assert!(shell.is_event_captured());
+        assert_eq!((top.get(), bottom.get()), (1, 0));
+
+        let mut column = Vertical::with_children(vec![
+            Element::new(Probe {
+                events: bottom.clone(),
+                capture: false,
+            }),
+            button("Ok").on_click(1).into(),
+        ]);
+        column.layout(Area::new(0, 0, 10, 4));
+        let mut shell = Shell::new();
+        column.process_event(click(2, 2), &mut shell);
+        assert!(shell.is_event_captured());
+        assert_eq!(bottom.get(), 0);
+
+        // A click next to the button goes on to the other child.
+        let mut shell = Shell::new();
+        column.process_event(click(8, 2), &mut shell);
+        assert!(!shell.is_event_captured());
+        assert_eq!(bottom.get(), 1);
+    }
+}